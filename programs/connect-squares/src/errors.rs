@@ -8,6 +8,22 @@ pub enum ProgramError {
     AlreadyInitialized,
     #[msg("insufficient funds")]
     InsufficientFunds,
+    #[msg("amount must be greater than 0")]
+    AmountMustBeGreaterThanZero,
+    #[msg("nothing to withdraw")]
+    NothingToWithdraw,
+    #[msg("too many payees specified")]
+    TooManyPayees,
+    #[msg("payee weight must be greater than 0")]
+    PayeeWeightMustBeGreaterThanZero,
+    #[msg("no payees have been configured")]
+    NoPayeesConfigured,
+    #[msg("payee accounts don't match the configured payees")]
+    PayeeAccountsMismatch,
+    #[msg("fee cannot be greater than 10000 basis points")]
+    FeeTooHigh,
+    #[msg("k-factor is too high")]
+    KFactorTooHigh,
 }
 
 #[error_code]
@@ -54,4 +70,98 @@ pub enum GameError {
     NotAuthorized,
     #[msg("cell value is invalid")]
     CellValueIsInvalid,
+    #[msg("game has not finished")]
+    GameNotFinished,
+    #[msg("refund accounts don't match the game's players")]
+    RefundAccountsMismatch,
+    #[msg("player has not joined this game")]
+    PlayerNotInGame,
+    #[msg("game has not expired")]
+    GameNotExpired,
+    #[msg("pot already has the maximum number of sponsors")]
+    TooManySponsors,
+    #[msg("doubling is only available in two player games with a wager")]
+    DoublingNotAvailable,
+    #[msg("a double has been offered and must be answered first")]
+    DoubleOfferPending,
+    #[msg("no double has been offered")]
+    NoDoubleOffered,
+    #[msg("only the owner of the doubling cube can offer a double")]
+    NotCubeOwner,
+    #[msg("the doubling cube is at its maximum value")]
+    CubeAtMaximum,
+    #[msg("a player cannot respond to their own double")]
+    CannotRespondToOwnDouble,
+    #[msg("payout table must have between 1 and maximum players entries")]
+    PayoutTableLengthInvalid,
+    #[msg("payout table percentages must add up to 100")]
+    PayoutTableTotalInvalid,
+    #[msg("payout accounts don't match the game's placements")]
+    PayoutAccountsMismatch,
+    #[msg("pot deposits don't match its payouts, fees and refunds")]
+    PotConservationViolated,
+    #[msg("backing is only available in games with a wager")]
+    BackingNotAvailable,
+    #[msg("players cannot back a game they are playing in")]
+    PlayersCannotBack,
+    #[msg("every backer has to claim before the game can be closed")]
+    BackersUnclaimed,
+    #[msg("betting is closed for this game")]
+    BettingClosed,
+    #[msg("players can only bet on themselves winning")]
+    CannotBetAgainstSelf,
+    #[msg("bet pool has not been settled")]
+    BetPoolNotSettled,
+    #[msg("bet pool has already been settled")]
+    BetPoolAlreadySettled,
+    #[msg("profile accounts don't match the game's players")]
+    ProfileAccountsMismatch,
+    #[msg("turn slots must be greater than 0")]
+    TurnSlotsMustBeGreaterThanZero,
+    #[msg("the queue is full")]
+    QueueFull,
+    #[msg("player is already queued")]
+    AlreadyQueued,
+    #[msg("player is not queued")]
+    NotQueued,
+    #[msg("players' ratings are too far apart to be matched")]
+    RatingsTooFarApart,
+    #[msg("lobby page is full")]
+    LobbyPageFull,
+    #[msg("the creator's game nonces are exhausted")]
+    NoncesExhausted,
+    #[msg("private game requires its invite key's signature to join")]
+    InviteRequired,
+    #[msg("only the challenged opponent can do that")]
+    NotChallenged,
+    #[msg("a direct challenge must be a two player game")]
+    ChallengeMustBeHeadsUp,
+    #[msg("creator cannot challenge themselves")]
+    CannotChallengeSelf,
+    #[msg("token gate amount must be greater than 0")]
+    GateAmountMustBeGreaterThanZero,
+    #[msg("token account isn't the joiner's account for the gate mint")]
+    InvalidGateTokenAccount,
+    #[msg("joiner doesn't hold enough of the gate mint")]
+    InsufficientGateBalance,
+    #[msg("player has already joined this game")]
+    PlayerAlreadyJoined,
+    #[msg("rematches are only available once a won game has been paid out")]
+    RematchNotAvailable,
+    #[msg("player has already agreed to a rematch")]
+    AlreadyAgreedToRematch,
+    #[msg("backing is closed for this game")]
+    BackingClosed,
+    #[msg("double offer has expired and can only be declined")]
+    DoubleOfferExpired,
+    #[msg("game has timed out and can only be closed")]
+    GameTimedOut,
+    #[msg("only players can close a won game while the rematch window is open")]
+    RematchWindowOpen,
+    #[msg("lobby lifetime must be between one second and a week")]
+    LobbyTtlOutOfRange,
+    #[msg("games can't end in a tie, so ties can't be bet on")]
+    TieBetsNotAvailable,
+    #[msg("free-to-play games can't be rated")]
+    FreeGamesCannotBeRated,
 }
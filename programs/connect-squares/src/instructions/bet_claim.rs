@@ -0,0 +1,53 @@
+use anchor_lang::{prelude::*, AccountsClose};
+use crate::{
+    state::{BetPool, Bet},
+    errors::GameError,
+    utils::transfer_owned_sol,
+};
+
+//the last claim closes the pool and returns its rent to whoever created it
+pub fn bet_claim_handler(ctx: Context<BetClaim>) -> Result<()> {
+    let bet = &ctx.accounts.bet;
+    let bet_pool = &mut ctx.accounts.bet_pool;
+    let bet_pool_account_info = &mut bet_pool.to_account_info();
+
+    let payout = bet_pool.claim(bet.get_outcome(), bet.get_amount())?;
+    transfer_owned_sol(bet_pool_account_info,
+        &mut ctx.accounts.bettor.to_account_info(),
+        payout)?;
+
+    if bet_pool.get_open_bets() == 0 {
+        bet_pool.close(ctx.accounts.pool_creator.to_account_info())?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct BetClaim<'info> {
+    #[account(
+        mut,
+        seeds = [b"bet_pool", bet_pool.get_game().as_ref()],
+        bump = bet_pool.get_bump(),
+    )]
+    pub bet_pool: Account<'info, BetPool>,
+
+    #[account(
+        mut,
+        seeds = [b"bet", bet_pool.key().as_ref(), bettor.key().as_ref()],
+        bump = bet.get_bump(),
+        close = bettor,
+    )]
+    pub bet: Account<'info, Bet>,
+
+    /// CHECK: the pool's creator paid its rent, so it's returned to them
+    #[account(
+        mut,
+        address = bet_pool.get_creator() @ GameError::NotAuthorized,
+    )]
+    pub pool_creator: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{
+        game::*,
+        BetPool,
+        Bet,
+        BetOutcome,
+    },
+    errors::ProgramError,
+    utils::transfer_sol,
+};
+
+//each bettor gets one bet per game
+pub fn bet_place_handler(ctx: Context<BetPlace>, outcome: BetOutcome, amount: u64) -> Result<()> {
+    require_gt!(amount, 0, ProgramError::AmountMustBeGreaterThanZero);
+
+    let game = &ctx.accounts.game;
+    let bettor = &ctx.accounts.bettor;
+    game.check_bet(bettor.key(), outcome)?;
+
+    let bet_pool = &mut ctx.accounts.bet_pool;
+    transfer_sol(&bettor.to_account_info(), &bet_pool.to_account_info(), amount)?;
+    bet_pool.place(outcome, amount, game.get_moves())?;
+
+    let bump = *ctx.bumps.get("bet").unwrap();
+    ctx.accounts.bet.init(bump, bet_pool.key(), bettor.key(), outcome, amount)
+}
+
+#[derive(Accounts)]
+pub struct BetPlace<'info> {
+    #[account(
+        seeds = [game.get_seed_prefix(), game.get_creator().as_ref(), &game.get_nonce().to_be_bytes()],
+        bump = game.get_bump(),
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(
+        mut,
+        seeds = [b"bet_pool", game.key().as_ref()],
+        bump = bet_pool.get_bump(),
+    )]
+    pub bet_pool: Account<'info, BetPool>,
+
+    #[account(
+        init,
+        payer = bettor,
+        space = 8 + Bet::SIZE,
+        seeds = [b"bet", bet_pool.key().as_ref(), bettor.key().as_ref()],
+        bump
+    )]
+    pub bet: Account<'info, Bet>,
+
+    #[account(mut)]
+    pub bettor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::{
+    game::*,
+    BetPool,
+    OutcomeTotal,
+};
+
+//bets are taken on the game until it reaches cutoff_moves moves
+pub fn bet_pool_init_handler(ctx: Context<BetPoolInit>, cutoff_moves: u8) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    game.open_bet_pool()?;
+
+    let bump = *ctx.bumps.get("bet_pool").unwrap();
+    ctx.accounts.bet_pool.init(bump, game.key(), ctx.accounts.creator.key(), cutoff_moves)
+}
+
+#[derive(Accounts)]
+pub struct BetPoolInit<'info> {
+    #[account(
+        mut,
+        seeds = [game.get_seed_prefix(), game.get_creator().as_ref(), &game.get_nonce().to_be_bytes()],
+        bump = game.get_bump(),
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    //one outcome for each player, plus a tie
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + BetPool::SIZE + (usize::from(game.get_max_players()) + 1) * OutcomeTotal::SIZE,
+        seeds = [b"bet_pool", game.key().as_ref()],
+        bump
+    )]
+    pub bet_pool: Account<'info, BetPool>,
+
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
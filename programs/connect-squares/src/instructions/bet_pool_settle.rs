@@ -0,0 +1,65 @@
+use anchor_lang::{prelude::*, AccountsClose};
+use crate::{
+    state::{
+        game::*,
+        BetPool,
+        Metadata,
+    },
+    errors::GameError,
+    utils::transfer_owned_sol,
+};
+
+//anyone can settle the pool once the game is over. the protocol fee goes to the treasury, and a pool nobody bet in is closed
+pub fn bet_pool_settle_handler(ctx: Context<BetPoolSettle>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let slot = Clock::get()?.slot;
+
+    let outcome = game.get_bet_outcome(slot)?;
+    game.settle_bet_pool();
+
+    let bet_pool = &mut ctx.accounts.bet_pool;
+    let fee = bet_pool.settle(outcome, ctx.accounts.metadata.get_fee_bps())?;
+
+    let bet_pool_account_info = &mut bet_pool.to_account_info();
+    transfer_owned_sol(bet_pool_account_info,
+        &mut ctx.accounts.metadata.to_account_info(),
+        fee)?;
+
+    if bet_pool.get_open_bets() == 0 {
+        bet_pool.close(ctx.accounts.pool_creator.to_account_info())?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct BetPoolSettle<'info> {
+    #[account(
+        mut,
+        seeds = [game.get_seed_prefix(), game.get_creator().as_ref(), &game.get_nonce().to_be_bytes()],
+        bump = game.get_bump(),
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(
+        mut,
+        seeds = [b"bet_pool", game.key().as_ref()],
+        bump = bet_pool.get_bump(),
+    )]
+    pub bet_pool: Account<'info, BetPool>,
+
+    #[account(
+        mut,
+        seeds = [b"metadata"],
+        bump = metadata.get_bump(),
+    )]
+    pub metadata: Account<'info, Metadata>,
+
+    /// CHECK: the pool's creator paid its rent, so it's returned to them
+    #[account(
+        mut,
+        address = bet_pool.get_creator() @ GameError::NotAuthorized,
+    )]
+    pub pool_creator: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{
+        game::*,
+        Pot,
+        Backing,
+    },
+    errors::ProgramError,
+    utils::transfer_sol,
+};
+
+//stakes lamports behind one of the game's players. each backer gets one position per game
+pub fn game_back_handler(ctx: Context<GameBack>, player: Pubkey, amount: u64) -> Result<()> {
+    require_gt!(amount, 0, ProgramError::AmountMustBeGreaterThanZero);
+
+    let backer = &ctx.accounts.backer;
+    let game = &mut ctx.accounts.game;
+    game.back(backer.key(), player, amount)?;
+
+    let pot = &mut ctx.accounts.pot;
+    transfer_sol(&backer.to_account_info(), &pot.to_account_info(), amount)?;
+    pot.back(amount)?;
+
+    let bump = *ctx.bumps.get("backing").unwrap();
+    ctx.accounts.backing.init(bump, game.key(), backer.key(), player, amount)
+}
+
+#[derive(Accounts)]
+pub struct GameBack<'info> {
+    #[account(
+        mut,
+        seeds = [game.get_seed_prefix(), game.get_creator().as_ref(), &game.get_nonce().to_be_bytes()],
+        bump = game.get_bump(),
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(
+        mut,
+        seeds = [b"pot", game.key().as_ref()],
+        bump = pot.bump,
+    )]
+    pub pot: Account<'info, Pot>,
+
+    #[account(
+        init,
+        payer = backer,
+        space = 8 + Backing::SIZE,
+        seeds = [b"backing", game.key().as_ref(), backer.key().as_ref()],
+        bump
+    )]
+    pub backing: Account<'info, Backing>,
+
+    #[account(mut)]
+    pub backer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{
+        game::*,
+        Pot,
+        Backing,
+    },
+    utils::debit_pot,
+};
+
+//backers of placed players share their winnings, backers of everyone else lose their stake.
+//games that end without a winner, and games still waiting for players, refund the stake instead.
+//anyone can claim on a backer's behalf, so an unclaimed position can't hold up closing the game
+pub fn game_back_claim_handler(ctx: Context<GameBackClaim>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let backing = &ctx.accounts.backing;
+    let slot = Clock::get()?.slot;
+
+    let (amount, debit) = game.claim_backing(backing.get_player(), backing.get_amount(), slot)?;
+
+    let pot = &mut ctx.accounts.pot;
+    let amount = pot.claim_backing(amount, game.get_backer_count() == 0)?;
+
+    debit_pot(pot, &mut ctx.accounts.backer.to_account_info(), amount, debit)
+}
+
+#[derive(Accounts)]
+pub struct GameBackClaim<'info> {
+    #[account(
+        mut,
+        seeds = [game.get_seed_prefix(), game.get_creator().as_ref(), &game.get_nonce().to_be_bytes()],
+        bump = game.get_bump(),
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(
+        mut,
+        seeds = [b"pot", game.key().as_ref()],
+        bump = pot.bump,
+    )]
+    pub pot: Account<'info, Pot>,
+
+    #[account(
+        mut,
+        seeds = [b"backing", game.key().as_ref(), backer.key().as_ref()],
+        bump = backing.get_bump(),
+        close = backer,
+    )]
+    pub backing: Account<'info, Backing>,
+
+    /// CHECK: the backing PDA is derived from the backer, who is paid the claim and the backing account's rent
+    #[account(
+        mut,
+        address = backing.get_backer(),
+    )]
+    pub backer: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -1,40 +1,55 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, AccountsClose};
 use crate::{
     state::{
         game::*,
-        Pot,
+        Lobby,
     },
-    errors::GameError
+    errors::GameError,
+    utils::refund_unstarted_game,
 };
 
 
-pub fn game_cancel_handler(ctx: Context<GameCancel>) -> Result<()> {
+//the pot's sponsors must be passed as remaining accounts in the order they first sponsored
+pub fn game_cancel_handler<'info>(ctx: Context<'_, '_, '_, 'info, GameCancel<'info>>) -> Result<()> {
     let game = &mut ctx.accounts.game;
-    let player = &ctx.accounts.player;
+    let player = &mut ctx.accounts.player.to_account_info();
 
-    game.cancel(player.key())
+    game.cancel(player.key())?;
+    ctx.accounts.lobby.remove(game.key());
+
+    //backers claim their refunds first, then game_close closes the game and pot
+    if !refund_unstarted_game(game, &ctx.accounts.pot.to_account_info(), player, ctx.remaining_accounts, ctx.program_id)? {
+        return Ok(());
+    }
+
+    game.close(player.clone())
 }
 
 #[derive(Accounts)]
 pub struct GameCancel<'info> {
     #[account(
         mut,
-        seeds = [b"game", game.get_creator().as_ref(), &game.get_nonce().to_be_bytes()],
+        seeds = [game.get_seed_prefix(), game.get_creator().as_ref(), &game.get_nonce().to_be_bytes()],
         bump = game.get_bump(),
-        close = player,
         constraint = player.key() == game.get_creator() @ GameError::NotAuthorized,
     )]
     pub game: Box<Account<'info, Game>>,
 
+    /// CHECK: the game's pot PDA, which doesn't exist for free-to-play games
     #[account(
         mut,
-        seeds = [b"pot", game.key().as_ref()],
-        bump=pot.bump,
-        close = player,
+        address = game.get_pot(),
     )]
-    pub pot: Account<'info, Pot>,
+    pub pot: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"lobby".as_ref(), game.get_lobby_page().to_be_bytes().as_ref()],
+        bump = lobby.get_bump(),
+    )]
+    pub lobby: Box<Account<'info, Lobby>>,
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{
+        game::*,
+        Metadata,
+    },
+    errors::GameError,
+    utils::{load_pot, refund_pot, close_pot, split_profile_accounts, record_results},
+};
+
+//when the game ended without a winner, every player's profile must be passed as a remaining account in seat order. if wagers
+//are still held by the pot, including deposits for a rematch that never started, the players' accounts follow in seat order,
+//then the pot's sponsors in the order they first sponsored
+pub fn game_close_handler<'info>(ctx: Context<'_, '_, '_, 'info, GameClose<'info>>) -> Result<()> {
+    let game = &ctx.accounts.game;
+    let slot = Clock::get()?.slot;
+
+    require!(game.is_closable(slot), GameError::GameNotFinished);
+    require!(!game.is_rematch_window_open(slot) || game.get_players().contains(&ctx.accounts.closer.key()), GameError::RematchWindowOpen);
+    require_eq!(game.get_backer_count(), 0, GameError::BackersUnclaimed);
+    require!(!game.is_bet_pool_open(), GameError::BetPoolNotSettled);
+
+    let records_result = game.requires_refund(slot);
+    let requires_refund = records_result || game.is_rematch_pending();
+    let mut refund_accounts = ctx.remaining_accounts;
+    if records_result {
+        let (profile_accounts, remaining_accounts) = split_profile_accounts(game, ctx.remaining_accounts)?;
+        record_results(game, profile_accounts, &[], ctx.accounts.metadata.get_k_factor(), ctx.program_id)?;
+        refund_accounts = remaining_accounts;
+    }
+
+    if game.is_free_to_play() {
+        return Ok(());
+    }
+
+    let pot = &mut load_pot(&ctx.accounts.pot.to_account_info())?;
+    if requires_refund {
+        refund_pot(pot,
+            &game.get_player_stakes(),
+            refund_accounts)?;
+    }
+
+    close_pot(pot, &mut ctx.accounts.creator.to_account_info())
+}
+
+#[derive(Accounts)]
+pub struct GameClose<'info> {
+    #[account(
+        mut,
+        seeds = [game.get_seed_prefix(), game.get_creator().as_ref(), &game.get_nonce().to_be_bytes()],
+        bump = game.get_bump(),
+        close = creator,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// CHECK: the game's pot PDA, which doesn't exist for free-to-play games
+    #[account(
+        mut,
+        address = game.get_pot(),
+    )]
+    pub pot: UncheckedAccount<'info>,
+
+    /// CHECK: the creator paid the rent for the game and pot accounts, so it's returned to them
+    #[account(
+        mut,
+        address = game.get_creator() @ GameError::NotAuthorized,
+    )]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"metadata"],
+        bump = metadata.get_bump(),
+    )]
+    pub metadata: Account<'info, Metadata>,
+
+    //anyone can close a finished game, but only its players while the rematch window is open
+    pub closer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,50 @@
+use anchor_lang::{prelude::*, AccountsClose};
+use crate::{
+    state::game::*,
+    errors::GameError,
+    utils::refund_unstarted_game,
+};
+
+
+//the pot's sponsors must be passed as remaining accounts in the order they first sponsored
+pub fn game_decline_handler<'info>(ctx: Context<'_, '_, '_, 'info, GameDecline<'info>>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let creator = &mut ctx.accounts.creator.to_account_info();
+
+    game.decline(ctx.accounts.opponent.key())?;
+
+    //backers claim their refunds first, then game_close closes the game and pot
+    if !refund_unstarted_game(game, &ctx.accounts.pot.to_account_info(), creator, ctx.remaining_accounts, ctx.program_id)? {
+        return Ok(());
+    }
+
+    game.close(creator.clone())
+}
+
+#[derive(Accounts)]
+pub struct GameDecline<'info> {
+    #[account(
+        mut,
+        seeds = [game.get_seed_prefix(), game.get_creator().as_ref(), &game.get_nonce().to_be_bytes()],
+        bump = game.get_bump(),
+        constraint = game.get_opponent() == Some(opponent.key()) @ GameError::NotChallenged,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// CHECK: the game's pot PDA, which doesn't exist for free-to-play games
+    #[account(
+        mut,
+        address = game.get_pot(),
+    )]
+    pub pot: UncheckedAccount<'info>,
+
+    pub opponent: Signer<'info>,
+
+    /// CHECK: the creator is refunded and paid the rent for the game and pot accounts, so it's returned to them
+    #[account(
+        mut,
+        address = game.get_creator(),
+    )]
+    pub creator: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
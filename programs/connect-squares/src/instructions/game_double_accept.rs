@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::Game,
+    utils::{load_pot, deposit_to_pot},
+};
+
+pub fn game_double_accept_handler(ctx: Context<GameDoubleAccept>) -> Result<()> {
+    let amount = ctx.accounts
+        .game
+        .accept_double(ctx.accounts.player.key())?;
+
+    let mut pot = load_pot(&ctx.accounts.pot.to_account_info())?;
+    deposit_to_pot(&ctx.accounts.player.to_account_info(), &mut pot, amount)?;
+    pot.exit(ctx.program_id)
+}
+
+#[derive(Accounts)]
+pub struct GameDoubleAccept<'info> {
+    #[account(
+        mut,
+        seeds = [game.get_seed_prefix(), game.get_creator().as_ref(), &game.get_nonce().to_be_bytes()],
+        bump = game.get_bump(),
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// CHECK: the game's pot PDA, doubling isn't available in free-to-play games so it always exists
+    #[account(
+        mut,
+        address = game.get_pot(),
+    )]
+    pub pot: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
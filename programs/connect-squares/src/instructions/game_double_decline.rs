@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{Game, Metadata, Jackpot},
+    errors::GameError,
+    utils::{load_pot, pay_placements, split_profile_accounts, record_results},
+};
+
+//every player's profile must be passed as a remaining account in seat order. when the game's payout table pays more than
+//the winner, the other placed players follow in finishing order
+pub fn game_double_decline_handler<'info>(ctx: Context<'_, '_, '_, 'info, GameDoubleDecline<'info>>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let winner = game.decline_double(ctx.accounts.player.key())?;
+
+    require_keys_eq!(winner, ctx.accounts.winner.key(), GameError::PlayerWinnerMismatch);
+
+    let (profile_accounts, placement_remaining_accounts) = split_profile_accounts(game, ctx.remaining_accounts)?;
+    let mut placement_accounts = vec![ctx.accounts.winner.to_account_info()];
+    placement_accounts.extend_from_slice(placement_remaining_accounts);
+
+    let mut pot = load_pot(&ctx.accounts.pot.to_account_info())?;
+    let payouts = pay_placements(game,
+        &mut pot,
+        &placement_accounts,
+        &ctx.accounts.metadata,
+        &mut ctx.accounts.jackpot)?;
+    pot.exit(ctx.program_id)?;
+
+    record_results(game, profile_accounts, &payouts, ctx.accounts.metadata.get_k_factor(), ctx.program_id)
+}
+
+#[derive(Accounts)]
+pub struct GameDoubleDecline<'info> {
+    #[account(
+        mut,
+        seeds = [game.get_seed_prefix(), game.get_creator().as_ref(), &game.get_nonce().to_be_bytes()],
+        bump = game.get_bump(),
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// CHECK: the game's pot PDA, doubling isn't available in free-to-play games so it always exists
+    #[account(
+        mut,
+        address = game.get_pot(),
+    )]
+    pub pot: UncheckedAccount<'info>,
+
+    //the responder, or anyone once the double has gone unanswered for a turn
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    /// CHECK: the player who offered the double, verified against the game in the handler
+    #[account(mut)]
+    pub winner: UncheckedAccount<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"metadata"],
+        bump = metadata.get_bump(),
+    )]
+    pub metadata: Account<'info, Metadata>,
+
+    #[account(
+        mut,
+        seeds = [b"jackpot"],
+        bump = jackpot.get_bump(),
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+
+    pub system_program: Program<'info, System>,
+}
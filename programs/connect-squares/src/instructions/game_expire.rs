@@ -0,0 +1,68 @@
+use anchor_lang::{prelude::*, AccountsClose};
+use crate::{
+    state::{
+        game::*,
+        Lobby,
+    },
+    errors::GameError,
+    utils::{transfer_owned_sol, refund_unstarted_game},
+};
+
+const EXPIRE_CRANK_TIP: u64 = 10000; //paid to the caller out of the game account's rent
+
+//the pot's sponsors must be passed as remaining accounts in the order they first sponsored
+pub fn game_expire_handler<'info>(ctx: Context<'_, '_, '_, 'info, GameExpire<'info>>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let now = Clock::get()?.unix_timestamp;
+
+    game.expire(now)?;
+    ctx.accounts.lobby.remove(game.key());
+
+    let creator = &mut ctx.accounts.creator.to_account_info();
+
+    //backers claim their refunds first, then game_close closes the game and pot. there's no tip as the game's rent stays put
+    if !refund_unstarted_game(game, &ctx.accounts.pot.to_account_info(), creator, ctx.remaining_accounts, ctx.program_id)? {
+        return Ok(());
+    }
+
+    transfer_owned_sol(&mut game.to_account_info(),
+        &mut ctx.accounts.caller.to_account_info(),
+        EXPIRE_CRANK_TIP)?;
+
+    game.close(creator.clone())
+}
+
+#[derive(Accounts)]
+pub struct GameExpire<'info> {
+    #[account(
+        mut,
+        seeds = [game.get_seed_prefix(), game.get_creator().as_ref(), &game.get_nonce().to_be_bytes()],
+        bump = game.get_bump(),
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// CHECK: the game's pot PDA, which doesn't exist for free-to-play games
+    #[account(
+        mut,
+        address = game.get_pot(),
+    )]
+    pub pot: UncheckedAccount<'info>,
+
+    /// CHECK: the creator paid the rent for the game and pot accounts, so it's returned to them
+    #[account(
+        mut,
+        address = game.get_creator() @ GameError::NotAuthorized,
+    )]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"lobby".as_ref(), game.get_lobby_page().to_be_bytes().as_ref()],
+        bump = lobby.get_bump(),
+    )]
+    pub lobby: Box<Account<'info, Lobby>>,
+    pub system_program: Program<'info, System>,
+}
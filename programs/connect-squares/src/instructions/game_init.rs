@@ -1,35 +1,46 @@
 use anchor_lang::prelude::*;
-use crate::{state::{game::*, Pot}};
-
-
-pub fn game_init_handler(ctx: Context<GameInit>, nonce: u32, rows: u8, cols: u8, connect: u8, min_players: u8, max_players: u8, wager: u32) -> Result<()> {
-     //transfer wager to pot
-     let from = ctx.accounts.creator.to_account_info();
-     let to = ctx.accounts.pot.to_account_info();
-     //transfer_sol(from, to, u64::from(wager))?;
-     let ix = anchor_lang::solana_program::system_instruction::transfer(
-        from.key,
-        to.key,
-        u64::from(wager),
-    );
-
-    anchor_lang::solana_program::program::invoke(
-        &ix,
-        &[from, to]
-    )?;
-
-    let pot = &mut ctx.accounts.pot;
-    let pot_bump = *ctx.bumps.get("pot").unwrap();
-    pot.init(pot_bump, ctx.accounts.game.key())?;
-
-    let bump = *ctx.bumps.get("game").unwrap();
-    let creator_key = ctx.accounts.creator.key();
-    ctx.accounts.game.init(bump, creator_key, nonce, pot.key(), rows, cols, connect, min_players, max_players, wager)
+use crate::{
+    state::{game::*, Pot, Lobby},
+    utils::transfer_sol,
+};
+
+
+pub fn game_init_handler(ctx: Context<GameInit>, nonce: u32, rows: u8, cols: u8, connect: u8, min_players: u8, max_players: u8, wager: u32, joiner_wager: u32, payout_table: Vec<u8>, rated: bool, invite_key: Option<Pubkey>, opponent: Option<Pubkey>, token_gate: Option<TokenGate>, lobby_ttl_seconds: Option<u32>) -> Result<()> {
+
+    init_game(&ctx.accounts.creator,
+        &mut ctx.accounts.game,
+        *ctx.bumps.get("game").unwrap(),
+        &mut ctx.accounts.pot,
+        *ctx.bumps.get("pot").unwrap(),
+        &mut ctx.accounts.lobby,
+        nonce, rows, cols, connect, min_players, max_players, wager, joiner_wager, payout_table, rated, invite_key, opponent, token_gate, lobby_ttl_seconds)
+}
+
+//shared with game_init_next, which only differs in where the nonce comes from
+pub fn init_game<'info>(creator: &Signer<'info>, game: &mut Account<'info, Game>, game_bump: u8, pot: &mut Account<'info, Pot>, pot_bump: u8, lobby: &mut Lobby, nonce: u32, rows: u8, cols: u8, connect: u8, min_players: u8, max_players: u8, wager: u32, joiner_wager: u32, payout_table: Vec<u8>, rated: bool, invite_key: Option<Pubkey>, opponent: Option<Pubkey>, token_gate: Option<TokenGate>, lobby_ttl_seconds: Option<u32>) -> Result<()> {
+    //transfer wager to pot
+    transfer_sol(&creator.to_account_info(), &pot.to_account_info(), u64::from(wager))?;
+
+    pot.init(pot_bump, game.key())?;
+    pot.deposit(u64::from(wager))?;
+
+    game.init(game_bump, creator.key(), nonce, pot.key(), rows, cols, connect, min_players, max_players, wager)?;
+    game.set_joiner_wager(joiner_wager)?;
+    game.set_rated(rated)?;
+    game.set_invite_key(invite_key)?;
+    game.set_opponent(opponent)?;
+    game.set_token_gate(token_gate)?;
+    if let Some(lobby_ttl_seconds) = lobby_ttl_seconds {
+        game.set_lobby_ttl(lobby_ttl_seconds)?;
+    }
+    let game_key = game.key();
+    game.list_in_lobby(game_key, lobby)?;
+    game.set_payout_table(payout_table)
 }
 
 
 #[derive(Accounts)]
-#[instruction(nonce: u32, rows: u8, cols: u8, connect: u8, min_players: u8, max_players: u8, wager: u32)]
+#[instruction(nonce: u32, rows: u8, cols: u8, connect: u8, min_players: u8, max_players: u8, wager: u32, joiner_wager: u32, payout_table: Vec<u8>)]
 pub struct GameInit<'info> {
     #[account(mut)]
     pub creator: Signer<'info>,
@@ -37,7 +48,7 @@ pub struct GameInit<'info> {
     #[account(
         init, 
         payer = creator,
-        space = 8 + Game::SIZE + usize::from(max_players * 32) + usize::from((4 * rows) * 2 + (rows * cols) * 2),
+        space = 8 + Game::SIZE + usize::from(max_players * 32) + usize::from(max_players) * (8 + 8 + 8 + 1 + 32) + usize::from((4 * rows) * 2 + (rows * cols) * 2),
         seeds = [b"game", creator.key().as_ref(), &nonce.to_be_bytes()],
         bump,
     )]
@@ -56,5 +67,12 @@ pub struct GameInit<'info> {
         bump,
     )]
     pub pot: Account<'info, Pot>,
+
+    #[account(
+        mut,
+        seeds = [b"lobby".as_ref(), lobby.get_page().to_be_bytes().as_ref()],
+        bump = lobby.get_bump(),
+    )]
+    pub lobby: Box<Account<'info, Lobby>>,
     pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::{game::*, Lobby};
+
+
+pub fn game_init_free_handler(ctx: Context<GameInitFree>, nonce: u32, rows: u8, cols: u8, connect: u8, min_players: u8, max_players: u8, rated: bool) -> Result<()> {
+
+    //the pot is never created, but its address is kept so every game instruction can take the same accounts
+    let game_key = ctx.accounts.game.key();
+    let (pot, _pot_bump) = Pubkey::find_program_address(&[b"pot", game_key.as_ref()], ctx.program_id);
+
+    let bump = *ctx.bumps.get("game").unwrap();
+    let creator_key = ctx.accounts.creator.key();
+    let game = &mut ctx.accounts.game;
+    game.init_free_to_play(bump, creator_key, nonce, pot, rows, cols, connect, min_players, max_players)?;
+    game.set_rated(rated)?;
+    game.list_in_lobby(game_key, &mut ctx.accounts.lobby)
+}
+
+
+#[derive(Accounts)]
+#[instruction(nonce: u32, rows: u8, cols: u8, connect: u8, min_players: u8, max_players: u8)]
+pub struct GameInitFree<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        init, 
+        payer = creator,
+        space = 8 + Game::SIZE + usize::from(max_players * 32) + usize::from(max_players) * (8 + 8 + 8 + 1 + 32) + usize::from((4 * rows) * 2 + (rows * cols) * 2),
+        seeds = [b"game", creator.key().as_ref(), &nonce.to_be_bytes()],
+        bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(
+        mut,
+        seeds = [b"lobby".as_ref(), lobby.get_page().to_be_bytes().as_ref()],
+        bump = lobby.get_bump(),
+    )]
+    pub lobby: Box<Account<'info, Lobby>>,
+    pub system_program: Program<'info, System>,
+}
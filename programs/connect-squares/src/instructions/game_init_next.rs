@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{game::*, Pot, Lobby, PlayerProfile},
+    instructions::init_game,
+};
+
+//same as game_init, but the nonce is taken from the creator's profile counter instead of chosen by the client.
+//counted games are derived under their own seed prefix, so they never collide with explicit nonces
+pub fn game_init_next_handler(ctx: Context<GameInitNext>, rows: u8, cols: u8, connect: u8, min_players: u8, max_players: u8, wager: u32, joiner_wager: u32, payout_table: Vec<u8>, rated: bool, invite_key: Option<Pubkey>, opponent: Option<Pubkey>, token_gate: Option<TokenGate>, lobby_ttl_seconds: Option<u32>) -> Result<()> {
+    let nonce = ctx.accounts.creator_profile.take_nonce()?;
+
+    init_game(&ctx.accounts.creator,
+        &mut ctx.accounts.game,
+        *ctx.bumps.get("game").unwrap(),
+        &mut ctx.accounts.pot,
+        *ctx.bumps.get("pot").unwrap(),
+        &mut ctx.accounts.lobby,
+        nonce, rows, cols, connect, min_players, max_players, wager, joiner_wager, payout_table, rated, invite_key, opponent, token_gate, lobby_ttl_seconds)?;
+
+    ctx.accounts.game.set_counted();
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(rows: u8, cols: u8, connect: u8, min_players: u8, max_players: u8)]
+pub struct GameInitNext<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", creator.key().as_ref()],
+        bump = creator_profile.get_bump(),
+    )]
+    pub creator_profile: Box<Account<'info, PlayerProfile>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Game::SIZE + usize::from(max_players * 32) + usize::from(max_players) * (8 + 8 + 8 + 1 + 32) + usize::from((4 * rows) * 2 + (rows * cols) * 2),
+        seeds = [b"counted-game", creator.key().as_ref(), &creator_profile.get_next_nonce().to_be_bytes()],
+        bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + Pot::SIZE,
+        seeds = [b"pot", game.key().as_ref()],
+        bump,
+    )]
+    pub pot: Account<'info, Pot>,
+
+    #[account(
+        mut,
+        seeds = [b"lobby".as_ref(), lobby.get_page().to_be_bytes().as_ref()],
+        bump = lobby.get_bump(),
+    )]
+    pub lobby: Box<Account<'info, Lobby>>,
+    pub system_program: Program<'info, System>,
+}
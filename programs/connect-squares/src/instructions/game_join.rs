@@ -1,44 +1,90 @@
 use anchor_lang::prelude::*;
-use crate::state::{Game, Pot};
-
-pub fn game_join_handler(ctx: Context<GameJoin>) -> Result<()> {
-    //transfer wager to pot    
-    let from = ctx.accounts.player.to_account_info();
-    let to = ctx.accounts.pot.to_account_info();
-    //transfer_sol(from, to, u64::from(wager))?;
-    let ix = anchor_lang::solana_program::system_instruction::transfer(
-       from.key,
-       to.key,
-       u64::from(ctx.accounts.game.get_wager()),
-   );
-
-    anchor_lang::solana_program::program::invoke(
-       &ix,
-       &[from, to]
-    )?;
-
-    ctx.accounts
-        .game
-        .join(ctx.accounts.player.key())
+use crate::{
+    state::{Game, PlayerProfile, Lobby},
+    errors::GameError,
+    utils::{load_pot, deposit_to_pot, token_balance},
+};
+
+//the joiner's token account for the gate mint must be the first remaining account if the game is token-gated,
+//and the invite key must be passed as a signing remaining account if the game is private
+pub fn game_join_handler<'info>(ctx: Context<'_, '_, '_, 'info, GameJoin<'info>>) -> Result<()> {
+    if let Some(token_gate) = ctx.accounts.game.get_token_gate() {
+        let token_account = ctx.remaining_accounts.first().ok_or(GameError::InvalidGateTokenAccount)?;
+        let balance = token_balance(token_account, &ctx.accounts.player.key(), &token_gate.mint)?;
+        require!(balance >= token_gate.amount, GameError::InsufficientGateBalance);
+    }
+
+    if !ctx.accounts.game.is_free_to_play() {
+        //transfer the joiner's wager to pot, which may differ from the creator's when odds are given
+        let mut pot = load_pot(&ctx.accounts.pot.to_account_info())?;
+        deposit_to_pot(&ctx.accounts.player.to_account_info(),
+            &mut pot,
+            u64::from(ctx.accounts.game.get_joiner_wager()))?;
+        pot.exit(ctx.program_id)?;
+    }
+
+    let invite_signed = match ctx.accounts.game.get_invite_key() {
+        Some(invite_key) => ctx.remaining_accounts.iter().any(|a| a.key() == invite_key && a.is_signer),
+        None => false,
+    };
+
+    let game = &mut ctx.accounts.game;
+    game.join(ctx.accounts.player.key(), invite_signed)?;
+
+    if game.is_active() {
+        ctx.accounts.lobby.remove(game.key());
+    }
+
+    //the creator's game counts from when the first opponent joins. a creator taking a repeat seat shares the creator's
+    //profile account, which is written back last, so the game is only counted there
+    if ctx.accounts.player_profile.key() != ctx.accounts.creator_profile.key() {
+        ctx.accounts.player_profile.record_game_started();
+    }
+    if game.get_player_count() == 2 {
+        ctx.accounts.creator_profile.record_game_started();
+    }
+
+    Ok(())
 }
 
 #[derive(Accounts)]
 pub struct GameJoin<'info> {
     #[account(
         mut,
-        seeds = [b"game", game.get_creator().as_ref(), &game.get_nonce().to_be_bytes()],
+        seeds = [game.get_seed_prefix(), game.get_creator().as_ref(), &game.get_nonce().to_be_bytes()],
         bump = game.get_bump()
     )]
     pub game: Box<Account<'info, Game>>,
 
+    /// CHECK: the game's pot PDA, which doesn't exist for free-to-play games
     #[account(
         mut,
-        seeds = [b"pot", game.key().as_ref()],
-        bump = pot.bump,
+        address = game.get_pot(),
     )]
-    pub pot: Account<'info, Pot>,
+    pub pot: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", player.key().as_ref()],
+        bump = player_profile.get_bump(),
+    )]
+    pub player_profile: Account<'info, PlayerProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", game.get_creator().as_ref()],
+        bump = creator_profile.get_bump(),
+    )]
+    pub creator_profile: Account<'info, PlayerProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"lobby".as_ref(), game.get_lobby_page().to_be_bytes().as_ref()],
+        bump = lobby.get_bump(),
+    )]
+    pub lobby: Box<Account<'info, Lobby>>,
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
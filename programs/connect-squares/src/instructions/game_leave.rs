@@ -0,0 +1,50 @@
+use anchor_lang::{prelude::*, AccountsClose};
+use crate::{
+    state::{Game, Lobby},
+    utils::refund_unstarted_game,
+};
+
+//the second player to join starts the game, so the creator is the only player who can leave a waiting game, which cancels it.
+//the pot's sponsors must be passed as remaining accounts in the order they first sponsored
+pub fn game_leave_handler<'info>(ctx: Context<'_, '_, '_, 'info, GameLeave<'info>>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let player = &mut ctx.accounts.player.to_account_info();
+
+    game.cancel(player.key())?;
+    ctx.accounts.lobby.remove(game.key());
+
+    //backers claim their refunds first, then game_close closes the game and pot
+    if !refund_unstarted_game(game, &ctx.accounts.pot.to_account_info(), player, ctx.remaining_accounts, ctx.program_id)? {
+        return Ok(());
+    }
+
+    game.close(player.clone())
+}
+
+#[derive(Accounts)]
+pub struct GameLeave<'info> {
+    #[account(
+        mut,
+        seeds = [game.get_seed_prefix(), game.get_creator().as_ref(), &game.get_nonce().to_be_bytes()],
+        bump = game.get_bump(),
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// CHECK: the game's pot PDA, which doesn't exist for free-to-play games
+    #[account(
+        mut,
+        address = game.get_pot(),
+    )]
+    pub pot: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"lobby".as_ref(), game.get_lobby_page().to_be_bytes().as_ref()],
+        bump = lobby.get_bump(),
+    )]
+    pub lobby: Box<Account<'info, Lobby>>,
+    pub system_program: Program<'info, System>,
+}
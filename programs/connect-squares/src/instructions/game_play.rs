@@ -3,15 +3,17 @@ use anchor_lang::prelude::*;
 use crate::{
     state::{
         game::*,
-        Pot, 
         Metadata,
+        Jackpot,
     },
     errors::GameError,
-    utils::transfer_owned_sol,
+    utils::{load_pot, pay_placements, pay_jackpot, split_profile_accounts, record_results},
 };
 
 
-pub fn game_play_handler(ctx: Context<GamePlay>, tile: Tile) -> Result<()> {
+//every player's profile must be passed as a remaining account in seat order. when the game's payout table pays more than
+//the winner, the other placed players follow in finishing order
+pub fn game_play_handler<'info>(ctx: Context<'_, '_, '_, 'info, GamePlay<'info>>, tile: Tile) -> Result<()> {
     let game = &mut ctx.accounts.game;
     let player = &ctx.accounts.player;
 
@@ -20,14 +22,28 @@ pub fn game_play_handler(ctx: Context<GamePlay>, tile: Tile) -> Result<()> {
     if let GameState::Won{winner} = game.get_state() {
         require_keys_eq!(winner, player.key(), GameError::PlayerWinnerMismatch);
 
-        //transfer pot to winner
-        let pot = &mut ctx.accounts.pot.to_account_info();
-        let winnings = game.get_wager() as u64 * game.get_player_count() as u64;
-        transfer_owned_sol(pot,
-            &mut player.to_account_info(),
-            winnings)?;
+        let (profile_accounts, placement_remaining_accounts) = split_profile_accounts(game, ctx.remaining_accounts)?;
+        if game.is_free_to_play() {
+            return record_results(game, profile_accounts, &[], ctx.accounts.metadata.get_k_factor(), ctx.program_id);
+        }
 
-        transfer_owned_sol(pot, &mut ctx.accounts.metadata.to_account_info(), pot.lamports())
+        let mut placement_accounts = vec![player.to_account_info()];
+        placement_accounts.extend_from_slice(placement_remaining_accounts);
+
+        let mut pot = load_pot(&ctx.accounts.pot.to_account_info())?;
+        let payouts = pay_placements(game,
+            &mut pot,
+            &placement_accounts,
+            &ctx.accounts.metadata,
+            &mut ctx.accounts.jackpot)?;
+        pot.exit(ctx.program_id)?;
+
+        if game.won_in_minimum_moves() && ctx.accounts.metadata.is_jackpot_game(game.get_cell_count(), game.get_min_seat_wager(), pot.fees) {
+            let max_amount = ctx.accounts.metadata.get_jackpot_win_cap(pot.fees);
+            pay_jackpot(&mut ctx.accounts.jackpot, &mut player.to_account_info(), max_amount)?;
+        }
+
+        record_results(game, profile_accounts, &payouts, ctx.accounts.metadata.get_k_factor(), ctx.program_id)
     } else {
         Ok(())
     }
@@ -39,17 +55,17 @@ pub fn game_play_handler(ctx: Context<GamePlay>, tile: Tile) -> Result<()> {
 pub struct GamePlay<'info> {
     #[account(
         mut,
-        seeds = [b"game", game.get_creator().as_ref(), &game.get_nonce().to_be_bytes()],
+        seeds = [game.get_seed_prefix(), game.get_creator().as_ref(), &game.get_nonce().to_be_bytes()],
         bump = game.get_bump(),
     )]
     pub game: Box<Account<'info, Game>>,
 
+    /// CHECK: the game's pot PDA, which doesn't exist for free-to-play games
     #[account(
         mut,
-        seeds = [b"pot", game.key().as_ref()],
-        bump=pot.bump,
+        address = game.get_pot(),
     )]
-    pub pot: Account<'info, Pot>,
+    pub pot: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub player: Signer<'info>,
@@ -61,6 +77,13 @@ pub struct GamePlay<'info> {
     )]
     pub metadata: Account<'info, Metadata>,
 
+    #[account(
+        mut,
+        seeds = [b"jackpot"],
+        bump = jackpot.get_bump(),
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+
     pub system_program: Program<'info, System>,
 }
 
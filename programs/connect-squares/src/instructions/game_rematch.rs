@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::Game,
+    utils::{load_pot, deposit_to_pot, record_games_started},
+};
+
+//every player's profile must be passed as a remaining account in seat order, for the vote that starts the rematch
+pub fn game_rematch_handler<'info>(ctx: Context<'_, '_, '_, 'info, GameRematch<'info>>) -> Result<()> {
+    let game = &mut ctx.accounts.game;
+    let first_vote = !game.is_rematch_pending();
+    let stake = game.agree_to_rematch(ctx.accounts.player.key())?;
+
+    if !game.is_free_to_play() {
+        let mut pot = load_pot(&ctx.accounts.pot.to_account_info())?;
+        if first_vote {
+            pot.reset()?;
+        }
+        deposit_to_pot(&ctx.accounts.player.to_account_info(), &mut pot, stake)?;
+        pot.exit(ctx.program_id)?;
+    }
+
+    if game.is_active() {
+        record_games_started(game, ctx.remaining_accounts, ctx.program_id)?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GameRematch<'info> {
+    #[account(
+        mut,
+        seeds = [game.get_seed_prefix(), game.get_creator().as_ref(), &game.get_nonce().to_be_bytes()],
+        bump = game.get_bump()
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    /// CHECK: the game's pot PDA, which doesn't exist for free-to-play games
+    #[account(
+        mut,
+        address = game.get_pot(),
+    )]
+    pub pot: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
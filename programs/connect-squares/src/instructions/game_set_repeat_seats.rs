@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{Game, Metadata},
+    errors::ProgramError,
+};
+
+pub fn game_set_repeat_seats_handler(ctx: Context<GameSetRepeatSeats>, repeat_seats: bool) -> Result<()> {
+    ctx.accounts.game.set_repeat_seats(repeat_seats)
+}
+
+#[derive(Accounts)]
+pub struct GameSetRepeatSeats<'info> {
+
+    #[account(
+        constraint = authority.key() == metadata.get_authority() @ ProgramError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"metadata"],
+        bump = metadata.get_bump(),
+    )]
+    pub metadata: Account<'info, Metadata>,
+
+    #[account(
+        mut,
+        seeds = [game.get_seed_prefix(), game.get_creator().as_ref(), &game.get_nonce().to_be_bytes()],
+        bump = game.get_bump(),
+    )]
+    pub game: Box<Account<'info, Game>>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{Metadata, Jackpot},
+    errors::ProgramError,
+};
+
+pub fn jackpot_init_handler(ctx: Context<JackpotInit>) -> Result<()> {
+    let bump = *ctx.bumps.get("jackpot").unwrap();
+    ctx.accounts.jackpot.init(bump)
+}
+
+#[derive(Accounts)]
+pub struct JackpotInit<'info> {
+    
+    #[account(
+        mut,
+        address = metadata.get_authority() @ ProgramError::Unauthorized,
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"metadata"],
+        bump = metadata.get_bump(),
+    )]
+    pub metadata: Account<'info, Metadata>,
+    
+    #[account(
+        init,
+        payer=authority,
+        space = 8 + Jackpot::SIZE,
+        seeds = [b"jackpot"],
+        bump
+    )]
+    pub jackpot: Account<'info, Jackpot>,
+    pub system_program: Program<'info, System>,
+}
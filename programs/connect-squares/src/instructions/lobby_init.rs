@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use crate::state::Lobby;
+
+//anyone can open the next page once the last one fills up
+pub fn lobby_init_handler(ctx: Context<LobbyInit>, page: u16) -> Result<()> {
+    let bump = *ctx.bumps.get("lobby").unwrap();
+    ctx.accounts.lobby.init(bump, page)
+}
+
+#[derive(Accounts)]
+#[instruction(page: u16)]
+pub struct LobbyInit<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Lobby::SIZE,
+        seeds = [b"lobby".as_ref(), page.to_be_bytes().as_ref()],
+        bump
+    )]
+    pub lobby: Box<Account<'info, Lobby>>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{Game, Pot, MatchQueue, PlayerProfile},
+    utils::transfer_owned_sol,
+};
+
+//permissionless: pairs the queue entries at first and second, moving both escrowed wagers into the new game's pot.
+//the payer fronts the rent for the game and pot, and gets it back when the game is closed
+pub fn match_make_handler(ctx: Context<MatchMake>, nonce: u32, first: u8, second: u8) -> Result<()> {
+
+    let now = Clock::get()?.unix_timestamp;
+    let queue = &mut ctx.accounts.queue;
+    let players = queue.match_entries(first, second, now)?;
+    let wager = u64::from(queue.get_wager());
+
+    let pot = &mut ctx.accounts.pot;
+    let pot_bump = *ctx.bumps.get("pot").unwrap();
+    pot.init(pot_bump, ctx.accounts.game.key())?;
+    for _ in players.iter() {
+        transfer_owned_sol(&mut queue.to_account_info(), &mut pot.to_account_info(), wager)?;
+        pot.deposit(wager)?;
+    }
+
+    let bump = *ctx.bumps.get("game").unwrap();
+    ctx.accounts.game.init_matched(bump,
+        ctx.accounts.payer.key(),
+        nonce,
+        pot.key(),
+        queue.get_rows(),
+        queue.get_cols(),
+        queue.get_connect(),
+        queue.get_wager(),
+        queue.get_turn_slots(),
+        &players)?;
+
+    ctx.accounts.first_profile.record_game_started();
+    ctx.accounts.second_profile.record_game_started();
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(nonce: u32, first: u8, second: u8)]
+pub struct MatchMake<'info> {
+    #[account(
+        mut,
+        seeds = [b"queue".as_ref(), queue.get_preset_seed().as_ref()],
+        bump = queue.get_bump(),
+    )]
+    pub queue: Box<Account<'info, MatchQueue>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Game::SIZE + 2 * 32 + 2 * (8 + 8 + 8 + 1 + 32) + usize::from(queue.get_rows()) * 8 + usize::from(queue.get_rows()) * usize::from(queue.get_cols()) * 2,
+        seeds = [b"game", payer.key().as_ref(), &nonce.to_be_bytes()],
+        bump,
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Pot::SIZE,
+        seeds = [b"pot", game.key().as_ref()],
+        bump,
+    )]
+    pub pot: Account<'info, Pot>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", queue.get_player(first).as_ref()],
+        bump = first_profile.get_bump(),
+    )]
+    pub first_profile: Account<'info, PlayerProfile>,
+
+    #[account(
+        mut,
+        seeds = [b"profile", queue.get_player(second).as_ref()],
+        bump = second_profile.get_bump(),
+    )]
+    pub second_profile: Account<'info, PlayerProfile>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::state::Metadata;
+
+pub fn metadata_set_fee_handler(ctx: Context<MetadataSetFee>, fee_bps: u16) -> Result<()> {
+    ctx.accounts.metadata.set_fee(ctx.accounts.authority.key(), fee_bps)
+}
+
+#[derive(Accounts)]
+pub struct MetadataSetFee<'info> {
+    
+    #[account()]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"metadata"],
+        bump = metadata.get_bump(),
+    )]
+    pub metadata: Account<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::state::Metadata;
+
+pub fn metadata_set_jackpot_handler(ctx: Context<MetadataSetJackpot>, fee_share_bps: u16, min_cells: u16, min_wager: u32, max_win_multiple: u16) -> Result<()> {
+    ctx.accounts.metadata.set_jackpot(ctx.accounts.authority.key(), fee_share_bps, min_cells, min_wager, max_win_multiple)
+}
+
+#[derive(Accounts)]
+pub struct MetadataSetJackpot<'info> {
+    
+    #[account()]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"metadata"],
+        bump = metadata.get_bump(),
+    )]
+    pub metadata: Account<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::state::Metadata;
+
+pub fn metadata_set_k_factor_handler(ctx: Context<MetadataSetKFactor>, k_factor: u16) -> Result<()> {
+    ctx.accounts.metadata.set_k_factor(ctx.accounts.authority.key(), k_factor)
+}
+
+#[derive(Accounts)]
+pub struct MetadataSetKFactor<'info> {
+    
+    #[account()]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"metadata"],
+        bump = metadata.get_bump(),
+    )]
+    pub metadata: Account<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+}
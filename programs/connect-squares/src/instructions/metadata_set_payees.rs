@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+use crate::state::{Metadata, Payee};
+
+pub fn metadata_set_payees_handler(ctx: Context<MetadataSetPayees>, payees: Vec<Payee>) -> Result<()> {
+    ctx.accounts.metadata.set_payees(ctx.accounts.authority.key(), payees)
+}
+
+#[derive(Accounts)]
+pub struct MetadataSetPayees<'info> {
+    
+    #[account()]
+    pub authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"metadata"],
+        bump = metadata.get_bump(),
+    )]
+    pub metadata: Account<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+}
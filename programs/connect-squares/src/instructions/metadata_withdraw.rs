@@ -1,18 +1,33 @@
 use anchor_lang::prelude::*;
 use crate::{
     state::Metadata,
-    utils::transfer_owned_sol,
+    utils::{transfer_owned_sol, rent_exempt_surplus},
     errors::ProgramError
 };
 
 pub fn metadata_withdraw_handler(ctx: Context<MetadataWithdraw>, amount: u64) -> Result<()> {
-    let metadata = &ctx.accounts.metadata;    
-    let authority_account_info = &mut ctx.accounts.authority.to_account_info();
+    require_gt!(amount, 0, ProgramError::AmountMustBeGreaterThanZero);
+
+    withdraw(ctx.accounts, Some(amount))
+}
+
+pub fn metadata_withdraw_all_handler(ctx: Context<MetadataWithdraw>) -> Result<()> {
+    withdraw(ctx.accounts, None)
+}
+
+//withdraws the requested amount, or everything above the rent-exempt minimum when no amount is given
+fn withdraw(accounts: &mut MetadataWithdraw, amount: Option<u64>) -> Result<()> {
+    let metadata = &accounts.metadata;    
+    let authority_account_info = &mut accounts.authority.to_account_info();
     
     require_keys_eq!(metadata.get_authority(), authority_account_info.key(), ProgramError::Unauthorized);
 
     let metadata_account_info = &mut metadata.to_account_info();
-    require_gt!(metadata_account_info.lamports() - amount, 100000, ProgramError::InsufficientFunds); //must have enough funds remaining for rent
+    let available = rent_exempt_surplus(metadata_account_info)?; //must have enough funds remaining for rent
+    require_gt!(available, 0, ProgramError::NothingToWithdraw);
+
+    let amount = amount.unwrap_or(available);
+    require_gte!(available, amount, ProgramError::InsufficientFunds);
     
     transfer_owned_sol(metadata_account_info, authority_account_info, amount)
 }
@@ -29,4 +44,4 @@ pub struct MetadataWithdraw<'info> {
     )]
     pub metadata: Account<'info, Metadata>,
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
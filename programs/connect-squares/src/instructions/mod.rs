@@ -1,17 +1,77 @@
 pub mod metadata_init;
 pub mod metadata_set_authority;
 pub mod metadata_withdraw;
+pub mod metadata_set_payees;
+pub mod metadata_set_fee;
+pub mod metadata_set_jackpot;
+pub mod metadata_set_k_factor;
+pub mod treasury_distribute;
+pub mod jackpot_init;
 pub mod game_init;
+pub mod game_init_free;
 pub mod game_cancel;
 pub mod game_join;
+pub mod game_leave;
 pub mod game_play;
+pub mod game_double_offer;
+pub mod game_double_accept;
+pub mod game_double_decline;
+pub mod game_close;
+pub mod game_expire;
+pub mod pot_sponsor;
+pub mod game_back;
+pub mod game_back_claim;
+pub mod bet_pool_init;
+pub mod bet_place;
+pub mod bet_pool_settle;
+pub mod bet_claim;
+pub mod profile_init;
+pub mod queue_init;
+pub mod queue_enqueue;
+pub mod queue_dequeue;
+pub mod match_make;
+pub mod lobby_init;
+pub mod game_init_next;
+pub mod game_decline;
+pub mod game_set_repeat_seats;
+pub mod game_rematch;
 
 
 
 pub use metadata_init::*;
 pub use metadata_set_authority::*;
 pub use metadata_withdraw::*;
+pub use metadata_set_payees::*;
+pub use metadata_set_fee::*;
+pub use metadata_set_jackpot::*;
+pub use metadata_set_k_factor::*;
+pub use treasury_distribute::*;
+pub use jackpot_init::*;
 pub use game_init::*;
+pub use game_init_free::*;
 pub use game_cancel::*;
 pub use game_join::*;
+pub use game_leave::*;
 pub use game_play::*;
+pub use game_double_offer::*;
+pub use game_double_accept::*;
+pub use game_double_decline::*;
+pub use game_close::*;
+pub use game_expire::*;
+pub use pot_sponsor::*;
+pub use game_back::*;
+pub use game_back_claim::*;
+pub use bet_pool_init::*;
+pub use bet_place::*;
+pub use bet_pool_settle::*;
+pub use bet_claim::*;
+pub use profile_init::*;
+pub use queue_init::*;
+pub use queue_enqueue::*;
+pub use queue_dequeue::*;
+pub use match_make::*;
+pub use lobby_init::*;
+pub use game_init_next::*;
+pub use game_decline::*;
+pub use game_set_repeat_seats::*;
+pub use game_rematch::*;
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{
+        game::*,
+        Pot,
+    },
+    errors::{GameError, ProgramError},
+    utils::transfer_sol,
+};
+
+pub fn pot_sponsor_handler(ctx: Context<PotSponsor>, amount: u64) -> Result<()> {
+    require_gt!(amount, 0, ProgramError::AmountMustBeGreaterThanZero);
+    require!(ctx.accounts.game.get_state() == GameState::Waiting, GameError::GameAlreadyStarted);
+
+    transfer_sol(&ctx.accounts.sponsor.to_account_info(), &ctx.accounts.pot.to_account_info(), amount)?;
+
+    ctx.accounts
+        .pot
+        .sponsor(ctx.accounts.sponsor.key(), amount)
+}
+
+#[derive(Accounts)]
+pub struct PotSponsor<'info> {
+    #[account(
+        seeds = [game.get_seed_prefix(), game.get_creator().as_ref(), &game.get_nonce().to_be_bytes()],
+        bump = game.get_bump(),
+    )]
+    pub game: Box<Account<'info, Game>>,
+
+    #[account(
+        mut,
+        seeds = [b"pot", game.key().as_ref()],
+        bump = pot.bump,
+    )]
+    pub pot: Account<'info, Pot>,
+
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
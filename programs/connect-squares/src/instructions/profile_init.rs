@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use crate::state::PlayerProfile;
+
+//anyone can create a player's profile, so a missing one can never hold up a game's settlement
+pub fn profile_init_handler(ctx: Context<ProfileInit>, player: Pubkey) -> Result<()> {
+    let bump = *ctx.bumps.get("profile").unwrap();
+    ctx.accounts.profile.init(bump, player)
+}
+
+#[derive(Accounts)]
+#[instruction(player: Pubkey)]
+pub struct ProfileInit<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PlayerProfile::SIZE,
+        seeds = [b"profile", player.as_ref()],
+        bump
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::MatchQueue,
+    utils::transfer_owned_sol,
+};
+
+//leaving the queue returns the escrowed wager
+pub fn queue_dequeue_handler(ctx: Context<QueueDequeue>) -> Result<()> {
+    let queue = &mut ctx.accounts.queue;
+    let player = &ctx.accounts.player;
+
+    queue.dequeue(player.key())?;
+
+    transfer_owned_sol(&mut queue.to_account_info(),
+        &mut player.to_account_info(),
+        u64::from(queue.get_wager()))
+}
+
+#[derive(Accounts)]
+pub struct QueueDequeue<'info> {
+    #[account(
+        mut,
+        seeds = [b"queue".as_ref(), queue.get_preset_seed().as_ref()],
+        bump = queue.get_bump(),
+    )]
+    pub queue: Box<Account<'info, MatchQueue>>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
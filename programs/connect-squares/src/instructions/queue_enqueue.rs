@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::{MatchQueue, PlayerProfile},
+    utils::transfer_sol,
+};
+
+//the player's wager is escrowed by the queue, and they're matched on the rating in their profile
+pub fn queue_enqueue_handler(ctx: Context<QueueEnqueue>) -> Result<()> {
+    let queue = &mut ctx.accounts.queue;
+    let player = &ctx.accounts.player;
+    let now = Clock::get()?.unix_timestamp;
+
+    transfer_sol(&player.to_account_info(), &queue.to_account_info(), u64::from(queue.get_wager()))?;
+
+    queue.enqueue(player.key(), ctx.accounts.profile.get_rating(), now)
+}
+
+#[derive(Accounts)]
+pub struct QueueEnqueue<'info> {
+    #[account(
+        mut,
+        seeds = [b"queue".as_ref(), queue.get_preset_seed().as_ref()],
+        bump = queue.get_bump(),
+    )]
+    pub queue: Box<Account<'info, MatchQueue>>,
+
+    #[account(
+        seeds = [b"profile", player.key().as_ref()],
+        bump = profile.get_bump(),
+    )]
+    pub profile: Account<'info, PlayerProfile>,
+
+    #[account(mut)]
+    pub player: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+use crate::state::MatchQueue;
+
+//anyone can open a queue for a rules preset
+pub fn queue_init_handler(ctx: Context<QueueInit>, rows: u8, cols: u8, connect: u8, wager: u32, turn_slots: u8) -> Result<()> {
+    let bump = *ctx.bumps.get("queue").unwrap();
+    ctx.accounts.queue.init(bump, rows, cols, connect, wager, turn_slots)
+}
+
+#[derive(Accounts)]
+#[instruction(rows: u8, cols: u8, connect: u8, wager: u32, turn_slots: u8)]
+pub struct QueueInit<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + MatchQueue::SIZE,
+        seeds = [b"queue".as_ref(), MatchQueue::preset_seed(rows, cols, connect, wager, turn_slots).as_ref()],
+        bump
+    )]
+    pub queue: Box<Account<'info, MatchQueue>>,
+    pub system_program: Program<'info, System>,
+}
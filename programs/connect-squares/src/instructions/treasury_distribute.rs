@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::Metadata,
+    utils::{transfer_owned_sol, rent_exempt_surplus},
+    errors::ProgramError
+};
+
+//payee accounts are passed as remaining accounts, in the same order as metadata.payees
+pub fn treasury_distribute_handler(ctx: Context<TreasuryDistribute>) -> Result<()> {
+    let metadata = &ctx.accounts.metadata;
+    let payees = metadata.get_payees().clone();
+    let total_weight = metadata.get_total_payee_weight();
+
+    require!(!payees.is_empty(), ProgramError::NoPayeesConfigured);
+    require_eq!(ctx.remaining_accounts.len(), payees.len(), ProgramError::PayeeAccountsMismatch);
+
+    let metadata_account_info = &mut metadata.to_account_info();
+    let distributable = rent_exempt_surplus(metadata_account_info)?; //never dip into the rent-exempt minimum
+    require_gt!(distributable, 0, ProgramError::InsufficientFunds);
+
+    for (payee, payee_account_info) in payees.iter().zip(ctx.remaining_accounts.iter()) {
+        require_keys_eq!(payee.address, payee_account_info.key(), ProgramError::PayeeAccountsMismatch);
+
+        //rounding remainders stay in the treasury for the next distribution
+        let amount = (distributable as u128 * payee.weight as u128 / total_weight as u128) as u64;
+        transfer_owned_sol(metadata_account_info, &mut payee_account_info.clone(), amount)?;
+        msg!("treasury paid {} lamports to {}", amount, payee.address);
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct TreasuryDistribute<'info> {
+    #[account(
+        mut,
+        seeds = [b"metadata"],
+        bump = metadata.get_bump(),
+    )]
+    pub metadata: Account<'info, Metadata>,
+    pub system_program: Program<'info, System>,
+}
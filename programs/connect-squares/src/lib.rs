@@ -4,7 +4,7 @@ pub mod instructions;
 use instructions::*;
 
 pub mod state;
-use crate::state::Tile;
+use crate::state::{Tile, Payee, BetOutcome, TokenGate};
 
 pub mod errors;
 pub mod utils;
@@ -28,19 +28,143 @@ pub mod connect_squares {
         instructions::metadata_withdraw_handler(ctx, amount)
     }
 
-    pub fn game_init(ctx: Context<GameInit>, nonce: u32, rows: u8, cols: u8, connect: u8, min_players: u8, max_players: u8, wager: u32) -> Result<()> {
-        instructions::game_init_handler(ctx, nonce, rows, cols, connect, min_players, max_players, wager)
+    pub fn metadata_withdraw_all(ctx: Context<MetadataWithdraw>) -> Result<()> {
+        instructions::metadata_withdraw_all_handler(ctx)
     }
 
-    pub fn game_cancel(ctx: Context<GameCancel>) -> Result<()> {
+    pub fn metadata_set_payees(ctx: Context<MetadataSetPayees>, payees: Vec<Payee>) -> Result<()> {
+        instructions::metadata_set_payees_handler(ctx, payees)
+    }
+
+    pub fn metadata_set_fee(ctx: Context<MetadataSetFee>, fee_bps: u16) -> Result<()> {
+        instructions::metadata_set_fee_handler(ctx, fee_bps)
+    }
+
+    pub fn metadata_set_jackpot(ctx: Context<MetadataSetJackpot>, fee_share_bps: u16, min_cells: u16, min_wager: u32, max_win_multiple: u16) -> Result<()> {
+        instructions::metadata_set_jackpot_handler(ctx, fee_share_bps, min_cells, min_wager, max_win_multiple)
+    }
+
+    pub fn metadata_set_k_factor(ctx: Context<MetadataSetKFactor>, k_factor: u16) -> Result<()> {
+        instructions::metadata_set_k_factor_handler(ctx, k_factor)
+    }
+
+    pub fn treasury_distribute(ctx: Context<TreasuryDistribute>) -> Result<()> {
+        instructions::treasury_distribute_handler(ctx)
+    }
+
+    pub fn jackpot_init(ctx: Context<JackpotInit>) -> Result<()> {
+        instructions::jackpot_init_handler(ctx)
+    }
+
+    pub fn game_init(ctx: Context<GameInit>, nonce: u32, rows: u8, cols: u8, connect: u8, min_players: u8, max_players: u8, wager: u32, joiner_wager: u32, payout_table: Vec<u8>, rated: bool, invite_key: Option<Pubkey>, opponent: Option<Pubkey>, token_gate: Option<TokenGate>, lobby_ttl_seconds: Option<u32>) -> Result<()> {
+        instructions::game_init_handler(ctx, nonce, rows, cols, connect, min_players, max_players, wager, joiner_wager, payout_table, rated, invite_key, opponent, token_gate, lobby_ttl_seconds)
+    }
+
+    pub fn game_init_free(ctx: Context<GameInitFree>, nonce: u32, rows: u8, cols: u8, connect: u8, min_players: u8, max_players: u8, rated: bool) -> Result<()> {
+        instructions::game_init_free_handler(ctx, nonce, rows, cols, connect, min_players, max_players, rated)
+    }
+
+    pub fn game_cancel<'info>(ctx: Context<'_, '_, '_, 'info, GameCancel<'info>>) -> Result<()> {
         instructions::game_cancel_handler(ctx)
     }
 
-    pub fn game_join(ctx: Context<GameJoin>) -> Result<()> {
+    pub fn game_join<'info>(ctx: Context<'_, '_, '_, 'info, GameJoin<'info>>) -> Result<()> {
         instructions::game_join_handler(ctx)
     }
 
-    pub fn game_play(ctx: Context<GamePlay>, tile: Tile) -> Result<()> {
+    pub fn game_leave<'info>(ctx: Context<'_, '_, '_, 'info, GameLeave<'info>>) -> Result<()> {
+        instructions::game_leave_handler(ctx)
+    }
+
+    pub fn game_play<'info>(ctx: Context<'_, '_, '_, 'info, GamePlay<'info>>, tile: Tile) -> Result<()> {
         instructions::game_play_handler(ctx, tile)
     }
+
+    pub fn game_double_offer(ctx: Context<GameDoubleOffer>) -> Result<()> {
+        instructions::game_double_offer_handler(ctx)
+    }
+
+    pub fn game_double_accept(ctx: Context<GameDoubleAccept>) -> Result<()> {
+        instructions::game_double_accept_handler(ctx)
+    }
+
+    pub fn game_double_decline<'info>(ctx: Context<'_, '_, '_, 'info, GameDoubleDecline<'info>>) -> Result<()> {
+        instructions::game_double_decline_handler(ctx)
+    }
+
+    pub fn game_close<'info>(ctx: Context<'_, '_, '_, 'info, GameClose<'info>>) -> Result<()> {
+        instructions::game_close_handler(ctx)
+    }
+
+    pub fn game_expire<'info>(ctx: Context<'_, '_, '_, 'info, GameExpire<'info>>) -> Result<()> {
+        instructions::game_expire_handler(ctx)
+    }
+
+    pub fn pot_sponsor(ctx: Context<PotSponsor>, amount: u64) -> Result<()> {
+        instructions::pot_sponsor_handler(ctx, amount)
+    }
+
+    pub fn game_back(ctx: Context<GameBack>, player: Pubkey, amount: u64) -> Result<()> {
+        instructions::game_back_handler(ctx, player, amount)
+    }
+
+    pub fn game_back_claim(ctx: Context<GameBackClaim>) -> Result<()> {
+        instructions::game_back_claim_handler(ctx)
+    }
+
+    pub fn bet_pool_init(ctx: Context<BetPoolInit>, cutoff_moves: u8) -> Result<()> {
+        instructions::bet_pool_init_handler(ctx, cutoff_moves)
+    }
+
+    pub fn bet_place(ctx: Context<BetPlace>, outcome: BetOutcome, amount: u64) -> Result<()> {
+        instructions::bet_place_handler(ctx, outcome, amount)
+    }
+
+    pub fn bet_pool_settle(ctx: Context<BetPoolSettle>) -> Result<()> {
+        instructions::bet_pool_settle_handler(ctx)
+    }
+
+    pub fn bet_claim(ctx: Context<BetClaim>) -> Result<()> {
+        instructions::bet_claim_handler(ctx)
+    }
+
+    pub fn profile_init(ctx: Context<ProfileInit>, player: Pubkey) -> Result<()> {
+        instructions::profile_init_handler(ctx, player)
+    }
+
+    pub fn queue_init(ctx: Context<QueueInit>, rows: u8, cols: u8, connect: u8, wager: u32, turn_slots: u8) -> Result<()> {
+        instructions::queue_init_handler(ctx, rows, cols, connect, wager, turn_slots)
+    }
+
+    pub fn queue_enqueue(ctx: Context<QueueEnqueue>) -> Result<()> {
+        instructions::queue_enqueue_handler(ctx)
+    }
+
+    pub fn queue_dequeue(ctx: Context<QueueDequeue>) -> Result<()> {
+        instructions::queue_dequeue_handler(ctx)
+    }
+
+    pub fn match_make(ctx: Context<MatchMake>, nonce: u32, first: u8, second: u8) -> Result<()> {
+        instructions::match_make_handler(ctx, nonce, first, second)
+    }
+
+    pub fn lobby_init(ctx: Context<LobbyInit>, page: u16) -> Result<()> {
+        instructions::lobby_init_handler(ctx, page)
+    }
+
+    pub fn game_init_next(ctx: Context<GameInitNext>, rows: u8, cols: u8, connect: u8, min_players: u8, max_players: u8, wager: u32, joiner_wager: u32, payout_table: Vec<u8>, rated: bool, invite_key: Option<Pubkey>, opponent: Option<Pubkey>, token_gate: Option<TokenGate>, lobby_ttl_seconds: Option<u32>) -> Result<()> {
+        instructions::game_init_next_handler(ctx, rows, cols, connect, min_players, max_players, wager, joiner_wager, payout_table, rated, invite_key, opponent, token_gate, lobby_ttl_seconds)
+    }
+
+    pub fn game_decline<'info>(ctx: Context<'_, '_, '_, 'info, GameDecline<'info>>) -> Result<()> {
+        instructions::game_decline_handler(ctx)
+    }
+
+    pub fn game_set_repeat_seats(ctx: Context<GameSetRepeatSeats>, repeat_seats: bool) -> Result<()> {
+        instructions::game_set_repeat_seats_handler(ctx, repeat_seats)
+    }
+
+    pub fn game_rematch<'info>(ctx: Context<'_, '_, '_, 'info, GameRematch<'info>>) -> Result<()> {
+        instructions::game_rematch_handler(ctx)
+    }
 }
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+#[account]
+pub struct Backing {
+    bump: u8, //1;
+    game: Pubkey, //32;
+    backer: Pubkey, //32;
+    player: Pubkey, //32; the player being backed
+    amount: u64, //8;
+}
+
+impl Backing {
+    pub const SIZE: usize = 1 + 32 + 32 + 32 + 8;
+
+    pub fn init(&mut self, bump: u8, game: Pubkey, backer: Pubkey, player: Pubkey, amount: u64) -> Result<()> {
+        self.bump = bump;
+        self.game = game;
+        self.backer = backer;
+        self.player = player;
+        self.amount = amount;
+
+        Ok(())
+    }
+
+    pub fn get_bump(&self) -> u8 {
+        self.bump
+    }
+
+    pub fn get_backer(&self) -> Pubkey {
+        self.backer
+    }
+
+    pub fn get_player(&self) -> Pubkey {
+        self.player
+    }
+
+    pub fn get_amount(&self) -> u64 {
+        self.amount
+    }
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::BetOutcome;
+
+#[account]
+pub struct Bet {
+    bump: u8, //1;
+    pool: Pubkey, //32;
+    bettor: Pubkey, //32;
+    outcome: BetOutcome, //1+32;
+    amount: u64, //8;
+}
+
+impl Bet {
+    pub const SIZE: usize = 1 + 32 + 32 + (1+32) + 8;
+
+    pub fn init(&mut self, bump: u8, pool: Pubkey, bettor: Pubkey, outcome: BetOutcome, amount: u64) -> Result<()> {
+        self.bump = bump;
+        self.pool = pool;
+        self.bettor = bettor;
+        self.outcome = outcome;
+        self.amount = amount;
+
+        Ok(())
+    }
+
+    pub fn get_bump(&self) -> u8 {
+        self.bump
+    }
+
+    pub fn get_outcome(&self) -> BetOutcome {
+        self.outcome
+    }
+
+    pub fn get_amount(&self) -> u64 {
+        self.amount
+    }
+}
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::metadata::MAX_FEE_BPS,
+    errors::GameError,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Copy)]
+pub enum BetOutcome {
+    Win { player: Pubkey },
+    Tie,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OutcomeTotal {
+    pub outcome: BetOutcome,
+    pub total: u64,
+}
+
+impl OutcomeTotal {
+    pub const SIZE: usize = (1+32) + 8;
+}
+
+//a parimutuel pool: everyone who bet on the final outcome shares everything bet, less the protocol fee
+#[account]
+pub struct BetPool {
+    bump: u8, //1;
+    game: Pubkey, //32;
+    creator: Pubkey, //32; paid the pool's rent, which is returned once every bet is claimed
+    cutoff_moves: u8, //1; bets are taken until the game reaches this many moves
+    settled: bool, //1;
+    winning_outcome: Option<BetOutcome>, //1+1+32; none when the pool is void and every bet is refunded
+    total: u64, //8;
+    winning_total: u64, //8;
+    fee: u64, //8;
+    paid_out: u64, //8;
+    open_bets: u32, //4;
+    outcome_totals: Vec<OutcomeTotal>, //4 + dynamic;
+}
+
+impl BetPool {
+    pub const SIZE: usize = 1 + 32 + 32 + 1 + 1 + (1+1+32) + 8 + 8 + 8 + 8 + 4 + 4;
+
+    pub fn init(&mut self, bump: u8, game: Pubkey, creator: Pubkey, cutoff_moves: u8) -> Result<()> {
+        self.bump = bump;
+        self.game = game;
+        self.creator = creator;
+        self.cutoff_moves = cutoff_moves;
+        self.settled = false;
+        self.winning_outcome = None;
+        self.total = 0;
+        self.winning_total = 0;
+        self.fee = 0;
+        self.paid_out = 0;
+        self.open_bets = 0;
+        self.outcome_totals = Vec::new();
+
+        Ok(())
+    }
+
+    pub fn place(&mut self, outcome: BetOutcome, amount: u64, moves: u16) -> Result<()> {
+        require!(!self.settled && moves < u16::from(self.cutoff_moves), GameError::BettingClosed);
+
+        self.total = self.total
+            .checked_add(amount)
+            .ok_or(GameError::PayoutCreditNumericalOverflow)?;
+        self.open_bets += 1;
+
+        match self.outcome_totals.iter_mut().find(|o| o.outcome == outcome) {
+            Some(outcome_total) => outcome_total.total += amount,
+            None => self.outcome_totals.push(OutcomeTotal { outcome, total: amount }),
+        }
+
+        Ok(())
+    }
+
+    //returns the protocol fee. when nobody bet on the outcome, or the game had no result, the pool is void
+    pub fn settle(&mut self, outcome: Option<BetOutcome>, fee_bps: u16) -> Result<u64> {
+        require!(!self.settled, GameError::BetPoolAlreadySettled);
+
+        let winning_total = outcome
+            .and_then(|outcome| self.outcome_totals.iter().find(|o| o.outcome == outcome))
+            .map(|o| o.total)
+            .unwrap_or(0);
+
+        self.settled = true;
+        if winning_total == 0 {
+            return Ok(0);
+        }
+
+        self.winning_outcome = outcome;
+        self.winning_total = winning_total;
+        self.fee = (self.total as u128 * fee_bps as u128 / MAX_FEE_BPS as u128) as u64;
+
+        Ok(self.fee)
+    }
+
+    //returns what the bet pays out. the last claim also takes whatever rounding is left over
+    pub fn claim(&mut self, outcome: BetOutcome, amount: u64) -> Result<u64> {
+        require!(self.settled, GameError::BetPoolNotSettled);
+
+        let payout = match self.winning_outcome {
+            None => amount,
+            Some(winning_outcome) if winning_outcome == outcome => {
+                ((self.total - self.fee) as u128 * amount as u128 / self.winning_total as u128) as u64
+            },
+            Some(_) => 0,
+        };
+
+        self.open_bets -= 1;
+        let payout = if self.open_bets == 0 {
+            self.total - self.fee - self.paid_out
+        } else {
+            payout
+        };
+        self.paid_out += payout;
+
+        Ok(payout)
+    }
+
+    pub fn get_bump(&self) -> u8 {
+        self.bump
+    }
+
+    pub fn get_game(&self) -> Pubkey {
+        self.game
+    }
+
+    pub fn get_creator(&self) -> Pubkey {
+        self.creator
+    }
+
+    pub fn get_open_bets(&self) -> u32 {
+        self.open_bets
+    }
+}
@@ -1,7 +1,17 @@
 use anchor_lang::prelude::*;
-use crate::errors::GameError;
-
-const PLAYER_TURN_MAX_SLOTS: u8 = 240;
+use crate::{
+    state::{PotDebit, BetOutcome, Lobby, LobbyEntry},
+    errors::GameError,
+};
+
+const PLAYER_TURN_MAX_SLOTS: u8 = 240; //default turn clock
+const GAME_TIMEOUT_TURNS: u64 = 100; //an active game with no moves for this many turns is considered abandoned
+const MAX_CUBE_VALUE: u8 = 64;
+pub const PAYOUT_TABLE_TOTAL: u8 = 100; //payout table entries are percentages of the pot
+const BACKING_CUTOFF_MOVES: u16 = 2; //backing closes after the first round of moves, well before anyone can be about to win
+const REMATCH_WINDOW_TURNS: u64 = 10; //only players can close a won game this soon after it ends, so a rematch can't be blocked
+const LOBBY_TTL_SECONDS: u32 = 60 * 60 * 24; //waiting games can be expired by anyone after this long, unless the creator picks otherwise
+const MAX_LOBBY_TTL_SECONDS: u32 = 60 * 60 * 24 * 7;
 const VERSION: u8 = 0;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Copy)]
@@ -13,6 +23,13 @@ pub enum GameState {
     Cancelled,
 }
 
+//joiners must hold at least amount of mint
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct TokenGate {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct Tile {
     row: u8,
@@ -25,40 +42,59 @@ pub struct Game {
     version: u8, //1;
     creator: Pubkey, //32;
     nonce: u32, //4;
+    counted: bool, //1; the nonce came from the creator's profile counter, which derives the game under its own seed prefix
     state: GameState, //1+32
     rows: u8, //1;
     cols: u8, //1;
     connect: u8, //1;
     min_players: u8, //1;
     max_players: u8, //1;
-    moves: u8, //1;
-    wager: u32, //4;
+    moves: u16, //2; keeps counting across board resets
+    wager: u32, //4; the creator's wager
+    joiner_wager: u32, //4; what everyone else wagers, which lets the creator give odds
     pot: Pubkey, //32;
+    free_to_play: bool, //1;
+    lobby_page: u16, //2; the open-lobby page listing the game while it's waiting for players
+    rated: bool, //1; rated games move the players' elo ratings
+    invite_key: Option<Pubkey>, //1+32; key whose signature a joiner must bring, private games only
+    opponent: Option<Pubkey>, //1+32; the only wallet that may take a direct challenge
+    token_gate: Option<TokenGate>, //1+32+8; token holding required to join
+    repeat_seats: bool, //1; set by the program authority to let one wallet fill several seats, e.g. for house games
+    turn_slots: u8, //1; slots each player has to move before their turn passes
     init_timestamp: i64, //8;
+    expires_at: i64, //8;
     last_move_slot: u64, //8;
     joined_players: u8, //1;
     current_player_index: u8, //1;
+    cube_value: u8, //1;
+    cube_owner: Option<Pubkey>, //1+32;
+    double_offered_by: Option<Pubkey>, //1+32;
+    backer_count: u32, //4; backing positions that have yet to be claimed
+    bet_pool_open: bool, //1; a side-betting pool has yet to be settled
+    rematch_votes: u8, //1; bitmask of the seats that have agreed to a rematch
     board: Vec<Vec<Option<u8>>>, //dynamic;
     players: Vec<Pubkey>, //dynamic;
+    stakes: Vec<u64>, //4 + dynamic; lamports each seat has put into the pot
+    payout_table: Vec<u8>, //4 + dynamic; percentage of the pot paid to each finishing position
+    placements: Vec<Pubkey>, //4 + dynamic; players in finishing order once the game is over
+    backing: Vec<u64>, //4 + dynamic; lamports backers have staked behind each seat
+    backer_payouts: Vec<u64>, //4 + dynamic; winnings each seat's backers share at settlement
 }
 
 impl Game {
-    pub const SIZE: usize = 1 + 1 + 32 + 4 + (1+32) + 1 + 1 + 1 + 1 + 1 + 1 + 4 + 32 + 8 + 8 + 1 + 1;
+    pub const SIZE: usize = 1 + 1 + 32 + 4 + 1 + (1+32) + 1 + 1 + 1 + 1 + 1 + 2 + 4 + 4 + 32 + 1 + 2 + 1 + (1+32) + (1+32) + (1+32+8) + 1 + 1 + 8 + 8 + 8 + 1 + 1 + 1 + (1+32) + (1+32) + 4 + 1 + 1 + 4 + 4 + 4 + 4 + 4;
 
     pub fn init(&mut self, bump: u8, creator: Pubkey, nonce: u32, pot:Pubkey, rows: u8, cols: u8, connect: u8, min_players: u8, max_players: u8, wager: u32) -> Result<()> {
-        require!(rows > 2, GameError::RowsMustBeGreaterThanTwo);
-        require!(cols > 2, GameError::ColumnsMustBeGreaterThanTwo);
+        Game::validate_board(rows, cols, connect)?;
         //only allow 2 players for now. More than two players allows collusion/cheating        
         require!(min_players > 1 && max_players > 1, GameError::MinimumPlayersMustBeGreaterThanOne);
         require!(min_players == 2 && max_players == 2 , GameError::TooManyPlayersSpecified);
-        require!(connect > 2, GameError::ConnectMinimumNotMet);
-        require!(connect <= rows, GameError::ConnectIsGreaterThanNumberOfRows);
-        require!(connect <= cols, GameError::ConnectIsGreaterThanNumberOfColumns);
 
         self.bump = bump;
         self.version = VERSION;
         self.creator = creator;
         self.nonce = nonce;
+        self.counted = false;
         self.state = GameState::Waiting;
         self.rows = rows;
         self.cols = cols;
@@ -67,19 +103,182 @@ impl Game {
         self.max_players = max_players;
         self.moves = 0;
         self.wager = wager;
+        self.joiner_wager = wager;
         self.pot = pot;
+        self.free_to_play = false;
+        self.lobby_page = 0;
+        self.rated = false;
+        self.invite_key = None;
+        self.opponent = None;
+        self.token_gate = None;
+        self.repeat_seats = false;
         self.last_move_slot = 0;
+        self.turn_slots = PLAYER_TURN_MAX_SLOTS;
         self.joined_players = 1;
         self.current_player_index = 0;
         self.players = vec![Pubkey::default(); max_players as usize];        
         self.init_timestamp = Clock::get()?.unix_timestamp;
+        self.expires_at = self.init_timestamp + i64::from(LOBBY_TTL_SECONDS);
         self.players[0] = creator;
+        self.stakes = vec![0; max_players as usize];
+        self.stakes[0] = wager as u64;
+        self.cube_value = 1;
+        self.cube_owner = None;
+        self.double_offered_by = None;
+        self.payout_table = vec![PAYOUT_TABLE_TOTAL]; //winner takes all
+        self.placements = Vec::new();
+        self.backer_count = 0;
+        self.bet_pool_open = false;
+        self.rematch_votes = 0;
+        self.backing = vec![0; max_players as usize];
+        self.backer_payouts = vec![0; max_players as usize];
 
         self.reset_board(rows, cols);
 
         Ok(())
     }
 
+    //matched games are rated and start straight away with every player's wager already escrowed.
+    //the creator only pays the rent, which is returned to them when the game is closed
+    pub fn init_matched(&mut self, bump: u8, creator: Pubkey, nonce: u32, pot:Pubkey, rows: u8, cols: u8, connect: u8, wager: u32, turn_slots: u8, players: &[Pubkey]) -> Result<()> {
+        let player_count = players.len() as u8;
+        self.init(bump, creator, nonce, pot, rows, cols, connect, player_count, player_count, wager)?;
+
+        self.rated = true;
+        self.turn_slots = turn_slots;
+        self.players = players.to_vec();
+        self.stakes = vec![wager as u64; players.len()];
+        self.joined_players = player_count;
+
+        self.start()
+    }
+
+    pub fn set_counted(&mut self) {
+        self.counted = true;
+    }
+
+    pub fn validate_board(rows: u8, cols: u8, connect: u8) -> Result<()> {
+        require!(rows > 2, GameError::RowsMustBeGreaterThanTwo);
+        require!(cols > 2, GameError::ColumnsMustBeGreaterThanTwo);
+        require!(connect > 2, GameError::ConnectMinimumNotMet);
+        require!(connect <= rows, GameError::ConnectIsGreaterThanNumberOfRows);
+        require!(connect <= cols, GameError::ConnectIsGreaterThanNumberOfColumns);
+
+        Ok(())
+    }
+
+    //free-to-play games have no wager and never create their pot account
+    pub fn init_free_to_play(&mut self, bump: u8, creator: Pubkey, nonce: u32, pot:Pubkey, rows: u8, cols: u8, connect: u8, min_players: u8, max_players: u8) -> Result<()> {
+        self.init(bump, creator, nonce, pot, rows, cols, connect, min_players, max_players, 0)?;
+        self.free_to_play = true;
+
+        Ok(())
+    }
+
+    pub fn set_payout_table(&mut self, payout_table: Vec<u8>) -> Result<()> {
+        require!(self.state == GameState::Waiting, GameError::GameAlreadyStarted);
+        require!(!payout_table.is_empty() && payout_table.len() <= self.max_players as usize, GameError::PayoutTableLengthInvalid);
+        require!(payout_table.iter().map(|p| *p as u16).sum::<u16>() == PAYOUT_TABLE_TOTAL as u16, GameError::PayoutTableTotalInvalid);
+
+        self.payout_table = payout_table;
+
+        Ok(())
+    }
+
+    pub fn set_joiner_wager(&mut self, joiner_wager: u32) -> Result<()> {
+        require!(self.state == GameState::Waiting, GameError::GameAlreadyStarted);
+
+        self.joiner_wager = joiner_wager;
+
+        Ok(())
+    }
+
+    pub fn list_in_lobby(&mut self, key: Pubkey, lobby: &mut Lobby) -> Result<()> {
+        //private games are only found through their invite link
+        if self.invite_key.is_some() || self.opponent.is_some() {
+            return Ok(());
+        }
+
+        lobby.add(LobbyEntry {
+            game: key,
+            rows: self.rows,
+            cols: self.cols,
+            connect: self.connect,
+            wager: self.wager,
+            joiner_wager: self.joiner_wager,
+        })?;
+        self.lobby_page = lobby.get_page();
+
+        Ok(())
+    }
+
+    //a free game risks nothing, so it can't be used to move ratings
+    pub fn set_rated(&mut self, rated: bool) -> Result<()> {
+        require!(self.state == GameState::Waiting, GameError::GameAlreadyStarted);
+        require!(!(rated && self.free_to_play), GameError::FreeGamesCannotBeRated);
+
+        self.rated = rated;
+
+        Ok(())
+    }
+
+    pub fn set_lobby_ttl(&mut self, lobby_ttl_seconds: u32) -> Result<()> {
+        require!(self.state == GameState::Waiting, GameError::GameAlreadyStarted);
+        require!(lobby_ttl_seconds > 0 && lobby_ttl_seconds <= MAX_LOBBY_TTL_SECONDS, GameError::LobbyTtlOutOfRange);
+
+        self.expires_at = self.init_timestamp + i64::from(lobby_ttl_seconds);
+
+        Ok(())
+    }
+
+    pub fn set_invite_key(&mut self, invite_key: Option<Pubkey>) -> Result<()> {
+        require!(self.state == GameState::Waiting, GameError::GameAlreadyStarted);
+
+        self.invite_key = invite_key;
+
+        Ok(())
+    }
+
+    pub fn set_opponent(&mut self, opponent: Option<Pubkey>) -> Result<()> {
+        require!(self.state == GameState::Waiting, GameError::GameAlreadyStarted);
+
+        if let Some(opponent) = opponent {
+            require!(self.max_players == 2, GameError::ChallengeMustBeHeadsUp);
+            require_keys_neq!(opponent, self.creator, GameError::CannotChallengeSelf);
+        }
+        self.opponent = opponent;
+
+        Ok(())
+    }
+
+    pub fn set_token_gate(&mut self, token_gate: Option<TokenGate>) -> Result<()> {
+        require!(self.state == GameState::Waiting, GameError::GameAlreadyStarted);
+
+        if let Some(token_gate) = token_gate {
+            require!(token_gate.amount > 0, GameError::GateAmountMustBeGreaterThanZero);
+        }
+        self.token_gate = token_gate;
+
+        Ok(())
+    }
+
+    pub fn set_repeat_seats(&mut self, repeat_seats: bool) -> Result<()> {
+        require!(self.state == GameState::Waiting, GameError::GameAlreadyStarted);
+
+        self.repeat_seats = repeat_seats;
+
+        Ok(())
+    }
+
+    pub fn decline(&mut self, player: Pubkey) -> Result<()> {
+        require!(self.state == GameState::Waiting, GameError::GameAlreadyStarted);
+        require!(self.opponent == Some(player), GameError::NotChallenged);
+
+        self.state = GameState::Cancelled;
+
+        Ok(())
+    }
+
     pub fn cancel(&mut self, player: Pubkey) -> Result<()> {
         require!(self.state == GameState::Waiting || self.state == GameState::Cancelled, GameError::GameAlreadyStarted);
         require_keys_eq!(self.creator, player, GameError::NotAuthorized);
@@ -89,23 +288,86 @@ impl Game {
         Ok(())
     }
 
-    pub fn join(&mut self, player: Pubkey) -> Result<()> {
+    pub fn expire(&mut self, now: i64) -> Result<()> {
+        require!(self.state == GameState::Waiting, GameError::GameAlreadyStarted);
+        require!(now >= self.expires_at, GameError::GameNotExpired);
+
+        self.state = GameState::Cancelled;
+
+        Ok(())
+    }
+
+    pub fn join(&mut self, player: Pubkey, invite_signed: bool) -> Result<()> {
         require!(self.state == GameState::Waiting, GameError::NotAcceptingPlayers);
+        if let Some(opponent) = self.opponent {
+            require_keys_eq!(opponent, player, GameError::NotChallenged);
+        }
+        require!(self.repeat_seats || !self.players[..self.joined_players as usize].contains(&player), GameError::PlayerAlreadyJoined);
+
+        //the invite keypair is shared off-chain with the invited players, and only its signature goes on-chain
+        require!(self.invite_key.is_none() || invite_signed, GameError::InviteRequired);
         
         self.players[self.joined_players as usize] = player;
+        self.stakes[self.joined_players as usize] = self.joiner_wager as u64;
         self.joined_players += 1;
 
         if self.joined_players == self.min_players {
-            self.shuffle_players()?;
-            self.state = GameState::Active;
-            self.last_move_slot =  Clock::get()?.slot;
+            self.start()?;
         }
 
         Ok(())
     }
 
+    fn start(&mut self) -> Result<()> {
+        self.shuffle_players()?;
+        self.state = GameState::Active;
+        self.last_move_slot =  Clock::get()?.slot;
+
+        Ok(())
+    }
+
+    //each player agrees to a rematch by re-depositing their wager, which is returned as their new stake.
+    //the board is reset and the players reshuffled once everyone has agreed
+    pub fn agree_to_rematch(&mut self, player: Pubkey) -> Result<u64> {
+        //votes and wagers are looked up by wallet, which can't tell a repeated wallet's seats apart
+        require!(matches!(self.state, GameState::Won{..}) && !self.repeat_seats, GameError::RematchNotAvailable);
+        require_eq!(self.backer_count, 0, GameError::BackersUnclaimed);
+        require!(!self.bet_pool_open, GameError::BetPoolNotSettled);
+
+        let seat = self.get_player_seat(player)?;
+        let vote = 1 << seat;
+        require!(self.rematch_votes & vote == 0, GameError::AlreadyAgreedToRematch);
+
+        //stakes from the last game were settled when it was paid out
+        if self.rematch_votes == 0 {
+            self.stakes = vec![0; self.max_players as usize];
+        }
+        self.rematch_votes |= vote;
+        let stake = self.get_seat_wager(seat) as u64;
+        self.stakes[seat] = stake;
+
+        if self.rematch_votes.count_ones() == self.joined_players as u32 {
+            self.moves = 0;
+            self.current_player_index = 0;
+            self.cube_value = 1;
+            self.cube_owner = None;
+            self.double_offered_by = None;
+            self.placements = Vec::new();
+            self.backing = vec![0; self.max_players as usize];
+            self.backer_payouts = vec![0; self.max_players as usize];
+            self.rematch_votes = 0;
+            self.reset_board(self.rows, self.cols);
+            self.start()?;
+        }
+
+        Ok(stake)
+    }
+
     pub fn play(&mut self, player: Pubkey, tile: &Tile) -> Result<()> {
         require!(self.is_active(), GameError::GameAlreadyOver);
+        //a timed out game is refunded by game_close, so it can't be revived with a late move
+        require!(!self.is_timed_out(Clock::get()?.slot), GameError::GameTimedOut);
+        require!(self.double_offered_by.is_none(), GameError::DoubleOfferPending);
         require!(tile.row < self.rows, GameError::TileOutOfBounds);
         require!(tile.column < self.cols, GameError::TileOutOfBounds);
 
@@ -118,11 +380,9 @@ impl Game {
         self.current_player_index = calculated_player_index as u8;
 
         if self.move_has_won(tile.row, tile.column) {
-            self.state = GameState::Won {
-                winner: player,
-            };            
+            self.declare_winner(player);
         }
-        else if self.moves == self.cols * self.rows {
+        else if self.board.iter().flatten().all(|cell| cell.is_some()) {
             //self.state = GameState::Tie;
             self.reset_board(self.rows, self.cols); //reset board. This is a deathmatch - ties don't exist.
         }
@@ -137,6 +397,186 @@ impl Game {
         Ok(())
     }
 
+    //returns the lamports the offering player has to add to the pot
+    pub fn offer_double(&mut self, player: Pubkey) -> Result<u64> {
+        require!(self.is_active(), GameError::GameAlreadyOver);
+        require!(!self.is_timed_out(Clock::get()?.slot), GameError::GameTimedOut);
+        require!(!self.free_to_play && self.max_players == 2 && !self.repeat_seats, GameError::DoublingNotAvailable);
+        require!(self.double_offered_by.is_none(), GameError::DoubleOfferPending);
+        require!(self.cube_value < MAX_CUBE_VALUE, GameError::CubeAtMaximum);
+        require!(self.cube_owner.is_none() || self.cube_owner == Some(player), GameError::NotCubeOwner);
+
+        let calculated_player_index = self.calculate_current_player_index();
+        require_keys_eq!(self.players[calculated_player_index], player, GameError::NotPlayersTurn);
+
+        //the offering player keeps their turn while the opponent decides
+        self.current_player_index = calculated_player_index as u8;
+        self.last_move_slot = Clock::get()?.slot;
+        self.double_offered_by = Some(player);
+
+        self.add_double_stake(calculated_player_index)
+    }
+
+    //returns the lamports the accepting player has to add to the pot
+    pub fn accept_double(&mut self, player: Pubkey) -> Result<u64> {
+        let seat = self.double_responder_seat(player)?;
+        require!(!self.is_double_expired(Clock::get()?.slot), GameError::DoubleOfferExpired);
+        let amount = self.add_double_stake(seat)?;
+
+        self.cube_value *= 2;
+        self.cube_owner = Some(player);
+        self.double_offered_by = None;
+        self.last_move_slot = Clock::get()?.slot;
+
+        Ok(amount)
+    }
+
+    //declining the double resigns the game at the current stake. a double left unanswered for a turn counts as declined,
+    //and then anyone can decline it, so the responder can't stall until the game times out and is refunded
+    pub fn decline_double(&mut self, caller: Pubkey) -> Result<Pubkey> {
+        require!(self.is_active(), GameError::GameAlreadyOver);
+        let offered_by = self.double_offered_by.ok_or(GameError::NoDoubleOffered)?;
+        if !self.is_double_expired(Clock::get()?.slot) {
+            self.double_responder_seat(caller)?;
+        }
+
+        self.declare_winner(offered_by);
+        self.double_offered_by = None;
+
+        Ok(offered_by)
+    }
+
+    //backing stays open while the game is waiting and for its first moves, so a joined player can be backed too
+    pub fn back(&mut self, backer: Pubkey, player: Pubkey, amount: u64) -> Result<()> {
+        let open = match self.state {
+            GameState::Waiting => true,
+            GameState::Active => self.moves < BACKING_CUTOFF_MOVES && !self.is_timed_out(Clock::get()?.slot),
+            _ => false,
+        };
+        require!(open, GameError::BackingClosed);
+        require!(!self.free_to_play, GameError::BackingNotAvailable);
+        require!(!self.get_players().contains(&backer), GameError::PlayersCannotBack);
+
+        let seat = self.get_player_seat(player)?;
+        self.backing[seat] = self.backing[seat]
+            .checked_add(amount)
+            .ok_or(GameError::PayoutCreditNumericalOverflow)?;
+        self.backer_count += 1;
+
+        Ok(())
+    }
+
+    //returns what a backer of the player is owed for their stake, and whether it's a refund or winnings
+    pub fn claim_backing(&mut self, player: Pubkey, amount: u64, slot: u64) -> Result<(u64, PotDebit)> {
+        let claim = match self.state {
+            GameState::Won{..} => {
+                let seat = self.get_player_seat(player)?;
+                let payout = self.backer_payouts[seat] as u128 * amount as u128 / self.backing[seat] as u128;
+
+                (payout as u64, PotDebit::Payout)
+            },
+            GameState::Waiting | GameState::Cancelled | GameState::Tie => self.refund_backing(player, amount)?,
+            GameState::Active if self.is_timed_out(slot) => self.refund_backing(player, amount)?,
+            GameState::Active => return Err(GameError::GameNotFinished.into()),
+        };
+        self.backer_count -= 1;
+
+        Ok(claim)
+    }
+
+    //takes the backers' proportion of a placed player's winnings, leaving the rest for the player
+    pub fn split_backer_share(&mut self, player: Pubkey, amount: u64) -> Result<u64> {
+        let seat = self.get_player_seat(player)?;
+        //an unbacked seat keeps everything, even when it staked nothing itself, e.g. in a sponsored game with no wager
+        if self.backing[seat] == 0 {
+            return Ok(0);
+        }
+
+        let seat_total = self.stakes[seat] as u128 + self.backing[seat] as u128;
+        let share = (amount as u128 * self.backing[seat] as u128 / seat_total) as u64;
+        self.backer_payouts[seat] += share;
+
+        Ok(share)
+    }
+
+    fn refund_backing(&mut self, player: Pubkey, amount: u64) -> Result<(u64, PotDebit)> {
+        let seat = self.get_player_seat(player)?;
+        self.backing[seat] -= amount;
+
+        Ok((amount, PotDebit::Refund))
+    }
+
+    //betting only opens once the players are seated, so nobody can bet and then join
+    pub fn open_bet_pool(&mut self) -> Result<()> {
+        require!(self.is_active(), GameError::BettingClosed);
+
+        self.bet_pool_open = true;
+
+        Ok(())
+    }
+
+    pub fn settle_bet_pool(&mut self) {
+        self.bet_pool_open = false;
+    }
+
+    //players can only bet on themselves winning. ties can't happen, a full board is reset and play goes on
+    pub fn check_bet(&self, bettor: Pubkey, outcome: BetOutcome) -> Result<()> {
+        require!(self.is_active(), GameError::BettingClosed);
+        require!(outcome != BetOutcome::Tie, GameError::TieBetsNotAvailable);
+
+        if let BetOutcome::Win{player} = outcome {
+            self.get_player_seat(player)?;
+        }
+
+        if self.get_players().contains(&bettor) {
+            require!(outcome == BetOutcome::Win{player: bettor}, GameError::CannotBetAgainstSelf);
+        }
+
+        Ok(())
+    }
+
+    //none when the game ended without a result
+    pub fn get_bet_outcome(&self, slot: u64) -> Result<Option<BetOutcome>> {
+        match self.state {
+            GameState::Won{winner} => Ok(Some(BetOutcome::Win{player: winner})),
+            GameState::Tie => Ok(Some(BetOutcome::Tie)),
+            _ if self.is_closable(slot) => Ok(None),
+            _ => Err(GameError::GameNotFinished.into()),
+        }
+    }
+
+    //the winner finishes first and everyone else follows in seat order, so heads up the loser is second
+    fn declare_winner(&mut self, winner: Pubkey) {
+        let mut placements = vec![winner];
+        placements.extend(self.get_players().iter().filter(|p| **p != winner));
+
+        self.placements = placements;
+        self.state = GameState::Won {
+            winner,
+        };
+    }
+
+    fn double_responder_seat(&self, player: Pubkey) -> Result<usize> {
+        require!(self.is_active(), GameError::GameAlreadyOver);
+
+        let offered_by = self.double_offered_by.ok_or(GameError::NoDoubleOffered)?;
+        require_keys_neq!(offered_by, player, GameError::CannotRespondToOwnDouble);
+
+        self.get_players()
+            .iter()
+            .position(|p| *p == player)
+            .ok_or_else(|| GameError::PlayerNotInGame.into())
+    }
+
+    fn add_double_stake(&mut self, seat: usize) -> Result<u64> {
+        let amount = self.get_seat_wager(seat) as u64 * self.cube_value as u64;
+        self.stakes[seat] = self.stakes[seat]
+            .checked_add(amount)
+            .ok_or(GameError::PayoutCreditNumericalOverflow)?;
+
+        Ok(amount)
+    }
+
     fn shuffle_players(&mut self) -> Result<()> {
         let player_count = self.players.len() as u64;
         let clock = Clock::get()?;
@@ -149,7 +589,9 @@ impl Game {
             //msg!("seed_a={},seed_b={}, a={}, b={}", seed_a, seed_b, a, b);
             //add some self.players.reverse()? in here to make it more of a shuffle for larger player counts?
             if a != b {
-                self.players.swap(a, b);                
+                self.players.swap(a, b);
+                self.stakes.swap(a, b);
+                self.backing.swap(a, b);
             }
         }
 
@@ -168,18 +610,194 @@ impl Game {
         self.nonce
     }
 
+    //counted games have their own prefix, so a caller-chosen nonce can never collide with one
+    pub fn get_seed_prefix(&self) -> &'static [u8] {
+        if self.counted {
+            b"counted-game"
+        } else {
+            b"game"
+        }
+    }
+
     pub fn get_state(&self) -> GameState {
         self.state
     }
 
+    pub fn get_pot(&self) -> Pubkey {
+        self.pot
+    }
+
+    pub fn is_free_to_play(&self) -> bool {
+        self.free_to_play
+    }
+
+    pub fn get_token_gate(&self) -> Option<TokenGate> {
+        self.token_gate
+    }
+
+    pub fn get_opponent(&self) -> Option<Pubkey> {
+        self.opponent
+    }
+
+    pub fn get_invite_key(&self) -> Option<Pubkey> {
+        self.invite_key
+    }
+
+    pub fn is_private(&self) -> bool {
+        self.invite_key.is_some()
+    }
+
+    pub fn get_lobby_page(&self) -> u16 {
+        self.lobby_page
+    }
+
+    pub fn is_rated(&self) -> bool {
+        self.rated
+    }
+
+    pub fn get_expires_at(&self) -> i64 {
+        self.expires_at
+    }
+
     pub fn get_wager(&self) -> u32 {
         self.wager
     }
 
+    pub fn get_joiner_wager(&self) -> u32 {
+        self.joiner_wager
+    }
+
+    pub fn get_min_seat_wager(&self) -> u32 {
+        self.wager.min(self.joiner_wager)
+    }
+
+    fn get_seat_wager(&self, seat: usize) -> u32 {
+        if self.players[seat] == self.creator {
+            self.wager
+        } else {
+            self.joiner_wager
+        }
+    }
+
     pub fn is_active(&self) -> bool {
         self.state == GameState::Active
     }
 
+    fn is_double_expired(&self, slot: u64) -> bool {
+        self.double_offered_by.is_some() && slot.saturating_sub(self.last_move_slot) >= self.turn_slots as u64
+    }
+
+    //a pending double never times out the game, it's settled by declining it instead
+    pub fn is_timed_out(&self, slot: u64) -> bool {
+        self.is_active() && self.double_offered_by.is_none() && slot.saturating_sub(self.last_move_slot) >= self.turn_slots as u64 * GAME_TIMEOUT_TURNS
+    }
+
+    pub fn is_closable(&self, slot: u64) -> bool {
+        match self.state {
+            GameState::Won{..} | GameState::Tie | GameState::Cancelled => true,
+            _ => self.is_timed_out(slot),
+        }
+    }
+
+    pub fn is_rematch_window_open(&self, slot: u64) -> bool {
+        matches!(self.state, GameState::Won{..})
+            && slot.saturating_sub(self.last_move_slot) < self.turn_slots as u64 * REMATCH_WINDOW_TURNS
+    }
+
+    pub fn is_rematch_pending(&self) -> bool {
+        self.rematch_votes != 0
+    }
+
+    //wagers are still held by the pot when a game ends without a winner being paid out
+    pub fn requires_refund(&self, slot: u64) -> bool {
+        self.state == GameState::Tie || self.is_timed_out(slot)
+    }
+
+    pub fn get_players(&self) -> &[Pubkey] {
+        &self.players[..self.joined_players as usize]
+    }
+
+    pub fn get_player_stakes(&self) -> Vec<(Pubkey, u64)> {
+        self.get_players()
+            .iter()
+            .copied()
+            .zip(self.stakes.iter().copied())
+            .collect()
+    }
+
+    pub fn get_total_stake(&self) -> u64 {
+        self.stakes.iter().sum()
+    }
+
+    pub fn get_total_backing(&self) -> u64 {
+        self.backing.iter().sum()
+    }
+
+    pub fn get_backer_count(&self) -> u32 {
+        self.backer_count
+    }
+
+    pub fn is_bet_pool_open(&self) -> bool {
+        self.bet_pool_open
+    }
+
+    pub fn get_moves(&self) -> u16 {
+        self.moves
+    }
+
+    pub fn get_max_players(&self) -> u8 {
+        self.max_players
+    }
+
+    fn get_player_seat(&self, player: Pubkey) -> Result<usize> {
+        self.get_players()
+            .iter()
+            .position(|p| *p == player)
+            .ok_or_else(|| GameError::PlayerNotInGame.into())
+    }
+
+    pub fn get_cell_count(&self) -> u16 {
+        self.rows as u16 * self.cols as u16
+    }
+
+    //true when the winner connected using no more moves than the connect length. the board only holds every move
+    //made while it has never been reset, which is when the winner's tiles are the winner's moves
+    pub fn won_in_minimum_moves(&self) -> bool {
+        let winner = match self.state {
+            GameState::Won{winner} => winner,
+            _ => return false,
+        };
+
+        if self.moves > self.get_cell_count() {
+            return false;
+        }
+
+        let seat = match self.get_players().iter().position(|p| *p == winner) {
+            Some(seat) => seat as u8,
+            None => return false,
+        };
+
+        let winner_tiles = self.board
+            .iter()
+            .flatten()
+            .filter(|cell| **cell == Some(seat))
+            .count();
+
+        winner_tiles <= self.connect as usize
+    }
+
+    pub fn get_payout_table(&self) -> &[u8] {
+        &self.payout_table
+    }
+
+    pub fn get_placements(&self) -> &[Pubkey] {
+        &self.placements
+    }
+
+    pub fn get_double_offered_by(&self) -> Option<Pubkey> {
+        self.double_offered_by
+    }
+
     pub fn get_player_count(&self) -> u8 {
         self.joined_players
     }
@@ -221,7 +839,7 @@ impl Game {
     fn calculate_current_player_index(&self)-> usize {
         let slot = Clock::get().unwrap().slot;
         let slot_diff = slot - self.last_move_slot;
-        let turns_passed =  slot_diff / (PLAYER_TURN_MAX_SLOTS as u64);
+        let turns_passed =  slot_diff / (self.turn_slots as u64);
         let mut player_index = self.current_player_index;
         let adder;
         if turns_passed >= self.joined_players as u64 {
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use crate::errors::GameError;
+
+#[account]
+pub struct Jackpot {
+    bump: u8, //1;
+    contributed: u64, //8;
+    paid_out: u64, //8;
+    wins: u32, //4;
+    last_winner: Pubkey, //32;
+}
+
+impl Jackpot {
+    pub const SIZE: usize = 1 + 8 + 8 + 4 + 32;
+
+    pub fn init(&mut self, bump: u8) -> Result<()> {
+        self.bump = bump;
+        self.contributed = 0;
+        self.paid_out = 0;
+        self.wins = 0;
+        self.last_winner = Pubkey::default();
+
+        Ok(())
+    }
+
+    pub fn contribute(&mut self, amount: u64) -> Result<()> {
+        self.contributed = self.contributed
+            .checked_add(amount)
+            .ok_or(GameError::PayoutCreditNumericalOverflow)?;
+
+        Ok(())
+    }
+
+    pub fn record_win(&mut self, winner: Pubkey, amount: u64) -> Result<()> {
+        self.paid_out = self.paid_out
+            .checked_add(amount)
+            .ok_or(GameError::PayoutCreditNumericalOverflow)?;
+        self.wins += 1;
+        self.last_winner = winner;
+
+        Ok(())
+    }
+
+    pub fn get_bump(&self) -> u8 {
+        self.bump
+    }
+}
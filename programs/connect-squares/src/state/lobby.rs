@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::errors::GameError;
+
+pub const LOBBY_PAGE_SIZE: usize = 32;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct LobbyEntry {
+    pub game: Pubkey, //32;
+    pub rows: u8, //1;
+    pub cols: u8, //1;
+    pub connect: u8, //1;
+    pub wager: u32, //4;
+    pub joiner_wager: u32, //4;
+}
+
+impl LobbyEntry {
+    pub const SIZE: usize = 32 + 1 + 1 + 1 + 4 + 4;
+}
+
+//one page of the open-lobby registry, listing games that are waiting for players
+#[account]
+pub struct Lobby {
+    bump: u8, //1;
+    page: u16, //2;
+    entries: Vec<LobbyEntry>, //4 + LOBBY_PAGE_SIZE * LobbyEntry::SIZE;
+}
+
+impl Lobby {
+    pub const SIZE: usize = 1 + 2 + (4 + LOBBY_PAGE_SIZE * LobbyEntry::SIZE);
+
+    pub fn init(&mut self, bump: u8, page: u16) -> Result<()> {
+        self.bump = bump;
+        self.page = page;
+        self.entries = Vec::new();
+
+        Ok(())
+    }
+
+    pub fn add(&mut self, entry: LobbyEntry) -> Result<()> {
+        require!(self.entries.len() < LOBBY_PAGE_SIZE, GameError::LobbyPageFull);
+
+        self.entries.push(entry);
+
+        Ok(())
+    }
+
+    pub fn remove(&mut self, game: Pubkey) {
+        self.entries.retain(|e| e.game != game);
+    }
+
+    pub fn get_bump(&self) -> u8 {
+        self.bump
+    }
+
+    pub fn get_page(&self) -> u16 {
+        self.page
+    }
+}
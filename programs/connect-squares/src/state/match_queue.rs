@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use crate::{
+    state::Game,
+    errors::GameError,
+};
+
+pub const MAX_QUEUE_ENTRIES: usize = 16;
+const RATING_WINDOW_BASE: u64 = 100;
+const RATING_WINDOW_GROWTH_PER_MINUTE: u64 = 25;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct QueueEntry {
+    pub player: Pubkey, //32;
+    pub rating: u16, //2;
+    pub enqueued_at: i64, //8;
+}
+
+impl QueueEntry {
+    pub const SIZE: usize = 32 + 2 + 8;
+
+    //the rating gap this entry accepts widens the longer it has waited
+    fn rating_window(&self, now: i64) -> u64 {
+        let minutes_waited = now.saturating_sub(self.enqueued_at).max(0) as u64 / 60;
+
+        RATING_WINDOW_BASE + minutes_waited * RATING_WINDOW_GROWTH_PER_MINUTE
+    }
+}
+
+//players waiting for a rated game with the same rules. their wagers are escrowed by the queue until they're matched
+#[account]
+pub struct MatchQueue {
+    bump: u8, //1;
+    rows: u8, //1;
+    cols: u8, //1;
+    connect: u8, //1;
+    wager: u32, //4;
+    turn_slots: u8, //1;
+    games_matched: u32, //4;
+    entries: Vec<QueueEntry>, //4 + MAX_QUEUE_ENTRIES * QueueEntry::SIZE;
+}
+
+impl MatchQueue {
+    pub const SIZE: usize = 1 + 1 + 1 + 1 + 4 + 1 + 4 + (4 + MAX_QUEUE_ENTRIES * QueueEntry::SIZE);
+
+    pub fn preset_seed(rows: u8, cols: u8, connect: u8, wager: u32, turn_slots: u8) -> [u8; 8] {
+        let wager = wager.to_be_bytes();
+
+        [rows, cols, connect, turn_slots, wager[0], wager[1], wager[2], wager[3]]
+    }
+
+    pub fn init(&mut self, bump: u8, rows: u8, cols: u8, connect: u8, wager: u32, turn_slots: u8) -> Result<()> {
+        Game::validate_board(rows, cols, connect)?;
+        require_gt!(turn_slots, 0, GameError::TurnSlotsMustBeGreaterThanZero);
+
+        self.bump = bump;
+        self.rows = rows;
+        self.cols = cols;
+        self.connect = connect;
+        self.wager = wager;
+        self.turn_slots = turn_slots;
+        self.games_matched = 0;
+        self.entries = Vec::new();
+
+        Ok(())
+    }
+
+    pub fn enqueue(&mut self, player: Pubkey, rating: u16, now: i64) -> Result<()> {
+        require!(self.entries.len() < MAX_QUEUE_ENTRIES, GameError::QueueFull);
+        require!(!self.entries.iter().any(|e| e.player == player), GameError::AlreadyQueued);
+
+        self.entries.push(QueueEntry {
+            player,
+            rating,
+            enqueued_at: now,
+        });
+
+        Ok(())
+    }
+
+    pub fn dequeue(&mut self, player: Pubkey) -> Result<()> {
+        let index = self.entries
+            .iter()
+            .position(|e| e.player == player)
+            .ok_or(GameError::NotQueued)?;
+        self.entries.remove(index);
+
+        Ok(())
+    }
+
+    //pairs two entries whose ratings are within the wider of their two windows, and returns the players
+    pub fn match_entries(&mut self, first: u8, second: u8, now: i64) -> Result<[Pubkey; 2]> {
+        let (first, second) = (first as usize, second as usize);
+        require!(first != second && first < self.entries.len() && second < self.entries.len(), GameError::NotQueued);
+
+        let (a, b) = (&self.entries[first], &self.entries[second]);
+        let rating_gap = (a.rating as i64 - b.rating as i64).unsigned_abs();
+        let window = a.rating_window(now).max(b.rating_window(now));
+        require_gte!(window, rating_gap, GameError::RatingsTooFarApart);
+
+        let players = [a.player, b.player];
+        self.entries.remove(first.max(second));
+        self.entries.remove(first.min(second));
+        self.games_matched += 1;
+
+        Ok(players)
+    }
+
+    pub fn get_bump(&self) -> u8 {
+        self.bump
+    }
+
+    pub fn get_rows(&self) -> u8 {
+        self.rows
+    }
+
+    pub fn get_cols(&self) -> u8 {
+        self.cols
+    }
+
+    pub fn get_connect(&self) -> u8 {
+        self.connect
+    }
+
+    pub fn get_wager(&self) -> u32 {
+        self.wager
+    }
+
+    pub fn get_turn_slots(&self) -> u8 {
+        self.turn_slots
+    }
+
+    pub fn get_preset_seed(&self) -> [u8; 8] {
+        MatchQueue::preset_seed(self.rows, self.cols, self.connect, self.wager, self.turn_slots)
+    }
+
+    //the queued player at index, or the default key when there isn't one
+    pub fn get_player(&self, index: u8) -> Pubkey {
+        self.entries
+            .get(index as usize)
+            .map(|e| e.player)
+            .unwrap_or_default()
+    }
+}
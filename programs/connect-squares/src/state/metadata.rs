@@ -1,15 +1,37 @@
 use anchor_lang::prelude::*;
 use crate::errors::ProgramError;
 
+pub const MAX_PAYEES: usize = 5;
+pub const MAX_FEE_BPS: u16 = 10000;
+pub const DEFAULT_K_FACTOR: u16 = 32;
+pub const MAX_K_FACTOR: u16 = 100;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Payee {
+    pub address: Pubkey, //32;
+    pub weight: u16, //2;
+}
+
+impl Payee {
+    pub const SIZE: usize = 32 + 2;
+}
+
 #[account]
 pub struct Metadata {
     bump: u8, //1;
     initialized: bool, //1;
     authority: Pubkey, //32;
+    payees: Vec<Payee>, //4 + MAX_PAYEES * Payee::SIZE;
+    fee_bps: u16, //2; protocol fee taken from every settled pot
+    jackpot_fee_share_bps: u16, //2; share of each fee that goes to the jackpot
+    jackpot_min_cells: u16, //2; smallest board that can win the jackpot, 0 disables it
+    jackpot_min_wager: u32, //4; smallest wager every seat must have staked to win the jackpot
+    jackpot_max_win_multiple: u16, //2; most a win pays, as a multiple of what the winning game put into the jackpot
+    k_factor: u16, //2; most a rated game can move a player's rating
 }
 
 impl Metadata {
-    pub const SIZE: usize = 1 + 1 + 32;
+    pub const SIZE: usize = 1 + 1 + 32 + (4 + MAX_PAYEES * Payee::SIZE) + 2 + 2 + 2 + 4 + 2 + 2;
 
     pub fn init(&mut self, bump: u8, authority: Pubkey) -> Result<()> {
         require!(!self.initialized, ProgramError::AlreadyInitialized);
@@ -17,6 +39,13 @@ impl Metadata {
         self.bump = bump;
         self.initialized = true;
         self.authority = authority; 
+        self.payees = Vec::new();
+        self.fee_bps = 0;
+        self.jackpot_fee_share_bps = 0;
+        self.jackpot_min_cells = 0;
+        self.jackpot_min_wager = 0;
+        self.jackpot_max_win_multiple = 0;
+        self.k_factor = DEFAULT_K_FACTOR;
 
         Ok(())
     }
@@ -29,6 +58,69 @@ impl Metadata {
         Ok(())
     }
 
+    pub fn set_payees(&mut self, authority: Pubkey, payees: Vec<Payee>) -> Result<()> {
+        require_keys_eq!(self.authority, authority, ProgramError::Unauthorized);
+        require!(payees.len() <= MAX_PAYEES, ProgramError::TooManyPayees);
+        require!(payees.iter().all(|p| p.weight > 0), ProgramError::PayeeWeightMustBeGreaterThanZero);
+
+        self.payees = payees;
+
+        Ok(())
+    }
+
+    pub fn set_fee(&mut self, authority: Pubkey, fee_bps: u16) -> Result<()> {
+        require_keys_eq!(self.authority, authority, ProgramError::Unauthorized);
+        require_gte!(MAX_FEE_BPS, fee_bps, ProgramError::FeeTooHigh);
+
+        self.fee_bps = fee_bps;
+
+        Ok(())
+    }
+
+    pub fn set_jackpot(&mut self, authority: Pubkey, fee_share_bps: u16, min_cells: u16, min_wager: u32, max_win_multiple: u16) -> Result<()> {
+        require_keys_eq!(self.authority, authority, ProgramError::Unauthorized);
+        require_gte!(MAX_FEE_BPS, fee_share_bps, ProgramError::FeeTooHigh);
+
+        self.jackpot_fee_share_bps = fee_share_bps;
+        self.jackpot_min_cells = min_cells;
+        self.jackpot_min_wager = min_wager;
+        self.jackpot_max_win_multiple = max_win_multiple;
+
+        Ok(())
+    }
+
+    pub fn set_k_factor(&mut self, authority: Pubkey, k_factor: u16) -> Result<()> {
+        require_keys_eq!(self.authority, authority, ProgramError::Unauthorized);
+        require_gte!(MAX_K_FACTOR, k_factor, ProgramError::KFactorTooHigh);
+
+        self.k_factor = k_factor;
+
+        Ok(())
+    }
+
+    //splits a settled game's fee into the jackpot's share and the treasury's share
+    pub fn split_fee(&self, fee: u64) -> (u64, u64) {
+        let jackpot_share = (fee as u128 * self.jackpot_fee_share_bps as u128 / MAX_FEE_BPS as u128) as u64;
+
+        (jackpot_share, fee - jackpot_share)
+    }
+
+    //only games that risked a real stake and paid a fee can win, so it can't be farmed with empty wagers
+    pub fn is_jackpot_game(&self, cells: u16, min_seat_wager: u32, fee: u64) -> bool {
+        self.jackpot_min_cells > 0
+            && self.jackpot_max_win_multiple > 0
+            && cells >= self.jackpot_min_cells
+            && min_seat_wager >= self.jackpot_min_wager
+            && fee > 0
+    }
+
+    //colluding players can always produce a quick win, so a win is capped by what their game's fee put into the jackpot
+    pub fn get_jackpot_win_cap(&self, fee: u64) -> u64 {
+        let (jackpot_share, _) = self.split_fee(fee);
+
+        jackpot_share.saturating_mul(self.jackpot_max_win_multiple as u64)
+    }
+
     pub fn get_bump(&self) -> u8 {
         self.bump
     }
@@ -36,4 +128,20 @@ impl Metadata {
     pub fn get_authority(&self) -> Pubkey {
         self.authority
     }
-}
\ No newline at end of file
+
+    pub fn get_fee_bps(&self) -> u16 {
+        self.fee_bps
+    }
+
+    pub fn get_k_factor(&self) -> u16 {
+        self.k_factor
+    }
+
+    pub fn get_payees(&self) -> &Vec<Payee> {
+        &self.payees
+    }
+
+    pub fn get_total_payee_weight(&self) -> u64 {
+        self.payees.iter().map(|p| p.weight as u64).sum()
+    }
+}
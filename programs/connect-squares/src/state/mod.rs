@@ -1,9 +1,23 @@
 pub use game::*;
 pub use metadata::*;
 pub use pot::*;
+pub use jackpot::*;
+pub use backing::*;
+pub use bet_pool::*;
+pub use bet::*;
+pub use player_profile::*;
+pub use match_queue::*;
+pub use lobby::*;
 
 
 pub mod game;
 pub mod metadata;
 pub mod pot;
+pub mod jackpot;
+pub mod backing;
+pub mod bet_pool;
+pub mod bet;
+pub mod player_profile;
+pub mod match_queue;
+pub mod lobby;
 
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use crate::errors::GameError;
+
+const INITIAL_RATING: u16 = 1200;
+const MIN_RATING: u16 = 100;
+const EXPECTED_SCORE_STEP: i32 = 50;
+const EXPECTED_SCORE_MAX_DIFFERENCE: i32 = 800;
+//per mille expected score against an opponent rated 0, 50, 100 ... 800 points lower
+const EXPECTED_SCORES: [i32; 17] = [500, 571, 640, 703, 760, 808, 849, 882, 909, 930, 947, 960, 969, 977, 983, 987, 990];
+
+pub enum GameResult {
+    Win,
+    Loss,
+    Tie,
+}
+
+#[account]
+pub struct PlayerProfile {
+    bump: u8, //1;
+    player: Pubkey, //32;
+    games_played: u32, //4;
+    wins: u32, //4;
+    losses: u32, //4;
+    ties: u32, //4; includes games abandoned without a winner
+    total_wagered: u64, //8;
+    total_won: u64, //8;
+    rating: u16, //2; elo, only moved by rated games
+    next_nonce: u32, //4; count of games created with game_init_next
+}
+
+impl PlayerProfile {
+    pub const SIZE: usize = 1 + 32 + 4 + 4 + 4 + 4 + 8 + 8 + 2 + 4;
+
+    pub fn init(&mut self, bump: u8, player: Pubkey) -> Result<()> {
+        self.bump = bump;
+        self.player = player;
+        self.games_played = 0;
+        self.wins = 0;
+        self.losses = 0;
+        self.ties = 0;
+        self.total_wagered = 0;
+        self.total_won = 0;
+        self.rating = INITIAL_RATING;
+        self.next_nonce = 0;
+
+        Ok(())
+    }
+
+    pub fn record_game_started(&mut self) {
+        self.games_played += 1;
+    }
+
+    pub fn record_result(&mut self, result: GameResult, wagered: u64, won: u64) -> Result<()> {
+        match result {
+            GameResult::Win => self.wins += 1,
+            GameResult::Loss => self.losses += 1,
+            GameResult::Tie => self.ties += 1,
+        }
+
+        self.total_wagered = self.total_wagered
+            .checked_add(wagered)
+            .ok_or(GameError::PayoutCreditNumericalOverflow)?;
+        self.total_won = self.total_won
+            .checked_add(won)
+            .ok_or(GameError::PayoutCreditNumericalOverflow)?;
+
+        Ok(())
+    }
+
+    pub fn rate(&mut self, opponent_rating: u16, result: &GameResult, k_factor: u16) {
+        let score = match result {
+            GameResult::Win => 1000,
+            GameResult::Loss => 0,
+            GameResult::Tie => 500,
+        };
+
+        let change = k_factor as i32 * (score - expected_score(self.rating, opponent_rating)) / 1000;
+        self.rating = (self.rating as i32 + change).clamp(MIN_RATING as i32, u16::MAX as i32) as u16;
+    }
+
+    pub fn take_nonce(&mut self) -> Result<u32> {
+        let nonce = self.next_nonce;
+        self.next_nonce = self.next_nonce
+            .checked_add(1)
+            .ok_or(GameError::NoncesExhausted)?;
+
+        Ok(nonce)
+    }
+
+    pub fn get_next_nonce(&self) -> u32 {
+        self.next_nonce
+    }
+
+    pub fn get_rating(&self) -> u16 {
+        self.rating
+    }
+
+    pub fn get_bump(&self) -> u8 {
+        self.bump
+    }
+
+    pub fn get_player(&self) -> Pubkey {
+        self.player
+    }
+}
+
+//integer elo: the expected score is interpolated from the table, rather than computed with floating point powers of ten
+fn expected_score(rating: u16, opponent_rating: u16) -> i32 {
+    let difference = (rating as i32 - opponent_rating as i32).abs().min(EXPECTED_SCORE_MAX_DIFFERENCE);
+    let index = (difference / EXPECTED_SCORE_STEP) as usize;
+    let lower = EXPECTED_SCORES[index];
+    let upper = EXPECTED_SCORES[(index + 1).min(EXPECTED_SCORES.len() - 1)];
+    let expected = lower + (upper - lower) * (difference % EXPECTED_SCORE_STEP) / EXPECTED_SCORE_STEP;
+
+    if rating >= opponent_rating {
+        expected
+    } else {
+        1000 - expected
+    }
+}
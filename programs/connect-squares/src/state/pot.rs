@@ -1,18 +1,154 @@
 use anchor_lang::prelude::*;
+use crate::errors::GameError;
+
+pub const MAX_SPONSORS: usize = 4;
+
+pub enum PotDebit {
+    Payout,
+    Fee,
+    Refund,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct Sponsor {
+    pub address: Pubkey, //32;
+    pub amount: u64, //8;
+}
+
+impl Sponsor {
+    pub const SIZE: usize = 32 + 8;
+}
 
 #[account]
 pub struct Pot {
     pub bump: u8, //1;
     pub game: Pubkey, //32;
+    pub sponsors: Vec<Sponsor>, //4 + MAX_SPONSORS * Sponsor::SIZE; in the order they first sponsored
+    pub sponsored: u64, //8;
+    pub deposited: u64, //8; wagers put in by players
+    pub backed: u64, //8; stakes put in by backers
+    pub backer_owed: u64, //8; lamports held for backers until they claim
+    pub paid_out: u64, //8;
+    pub fees: u64, //8;
+    pub refunded: u64, //8;
+    pub dust: u64, //8; lamports that arrived without going through the program, e.g. sent straight to the pot
 }
 
 impl Pot {
-    pub const SIZE: usize = 1 + 32;
+    pub const SIZE: usize = 1 + 32 + (4 + MAX_SPONSORS * Sponsor::SIZE) + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8;
 
     pub fn init(&mut self, bump: u8, game: Pubkey) -> Result<()> {
         self.bump = bump;
         self.game = game;
+        self.sponsors = Vec::new();
+        self.sponsored = 0;
+        self.deposited = 0;
+        self.backed = 0;
+        self.backer_owed = 0;
+        self.paid_out = 0;
+        self.fees = 0;
+        self.refunded = 0;
+        self.dust = 0;
+
+        Ok(())
+    }
+
+    //a pot is reused for a rematch once everything it held has been paid out
+    pub fn reset(&mut self) -> Result<()> {
+        require_eq!(self.get_outstanding(), 0, GameError::RematchNotAvailable);
+
+        self.init(self.bump, self.game)
+    }
+
+    //any account can sponsor a pot, up to MAX_SPONSORS of them, and each may top up its sponsorship any number of times
+    pub fn sponsor(&mut self, sponsor: Pubkey, amount: u64) -> Result<()> {
+        match self.sponsors.iter_mut().find(|s| s.address == sponsor) {
+            Some(existing) => {
+                existing.amount = existing.amount
+                    .checked_add(amount)
+                    .ok_or(GameError::PayoutCreditNumericalOverflow)?;
+            },
+            None => {
+                require_gt!(MAX_SPONSORS, self.sponsors.len(), GameError::TooManySponsors);
+                self.sponsors.push(Sponsor { address: sponsor, amount });
+            },
+        }
+
+        self.sponsored = self.sponsored
+            .checked_add(amount)
+            .ok_or(GameError::PayoutCreditNumericalOverflow)?;
+
+        Ok(())
+    }
+
+    pub fn deposit(&mut self, amount: u64) -> Result<()> {
+        self.deposited = self.deposited
+            .checked_add(amount)
+            .ok_or(GameError::PayoutCreditNumericalOverflow)?;
+
+        Ok(())
+    }
+
+    //a backer's stake is owed back to them until the game settles
+    pub fn back(&mut self, amount: u64) -> Result<()> {
+        self.backed = self.backed
+            .checked_add(amount)
+            .ok_or(GameError::PayoutCreditNumericalOverflow)?;
+        self.backer_owed += amount;
 
         Ok(())
     }
+
+    //at settlement backers are owed only their share of the winnings
+    pub fn set_backer_owed(&mut self, amount: u64) {
+        self.backer_owed = amount;
+    }
+
+    //pays out a backer's claim. the last claim also takes whatever rounding is left over
+    pub fn claim_backing(&mut self, amount: u64, last_claim: bool) -> Result<u64> {
+        require_gte!(self.backer_owed, amount, GameError::PotConservationViolated);
+
+        let amount = if last_claim { self.backer_owed } else { amount };
+        self.backer_owed -= amount;
+
+        Ok(amount)
+    }
+
+    //every lamport leaving the pot has to be accounted for by something that was put in
+    pub fn debit(&mut self, debit: PotDebit, amount: u64) -> Result<()> {
+        require_gte!(self.get_outstanding(), amount, GameError::PotConservationViolated);
+
+        let total = match debit {
+            PotDebit::Payout => &mut self.paid_out,
+            PotDebit::Fee => &mut self.fees,
+            PotDebit::Refund => &mut self.refunded,
+        };
+        *total += amount;
+
+        Ok(())
+    }
+
+    pub fn record_dust(&mut self, amount: u64) -> Result<()> {
+        self.dust = self.dust
+            .checked_add(amount)
+            .ok_or(GameError::PayoutCreditNumericalOverflow)?;
+
+        Ok(())
+    }
+
+    //deposits == payouts + fees + refunds, apart from what backers have yet to claim
+    pub fn require_settled(&self) -> Result<()> {
+        require_eq!(self.get_outstanding(), self.backer_owed, GameError::PotConservationViolated);
+
+        Ok(())
+    }
+
+    //lamports held on behalf of players, backers and the sponsor
+    pub fn get_outstanding(&self) -> u64 {
+        (self.deposited + self.backed + self.sponsored) - (self.paid_out + self.fees + self.refunded)
+    }
+
+    pub fn get_sponsors(&self) -> &[Sponsor] {
+        &self.sponsors
+    }
 }
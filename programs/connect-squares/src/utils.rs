@@ -1,5 +1,15 @@
-use anchor_lang::prelude::*;
-use crate::errors::GameError;
+use anchor_lang::{prelude::*, AccountsClose};
+use crate::{
+    state::{Game, GameState, Pot, PotDebit, Metadata, Jackpot, PlayerProfile, GameResult, game::PAYOUT_TABLE_TOTAL, metadata::MAX_FEE_BPS},
+    errors::GameError,
+};
+
+mod token_program {
+    anchor_lang::declare_id!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+}
+const TOKEN_ACCOUNT_LEN: usize = 165;
+const TOKEN_ACCOUNT_STATE_OFFSET: usize = 108;
+const TOKEN_ACCOUNT_UNINITIALIZED: u8 = 0;
 
 pub fn transfer_owned_sol(from: &mut AccountInfo, to: &mut AccountInfo, amount: u64) -> Result<()> {
     let post_from = from
@@ -17,22 +27,252 @@ pub fn transfer_owned_sol(from: &mut AccountInfo, to: &mut AccountInfo, amount:
 
     Ok(())
 }
-/*
-pub fn transfer_sol<'a>(from: &'a AccountInfo, to: &'a AccountInfo, amount: u64) -> Result<()> {
+
+pub fn load_pot<'info>(pot: &AccountInfo<'info>) -> Result<Account<'info, Pot>> {
+    Account::<Pot>::try_from(pot)
+}
+
+//moves lamports out of the pot, recording why so the pot's books always balance
+pub fn debit_pot(pot: &mut Account<Pot>, to: &mut AccountInfo, amount: u64, debit: PotDebit) -> Result<()> {
+    pot.debit(debit, amount)?;
+
+    transfer_owned_sol(&mut pot.to_account_info(), to, amount)
+}
+
+//lamports above the pot's rent and what it holds for players were never deposited through the program
+pub fn sweep_pot_dust(pot: &mut Account<Pot>, to: &mut AccountInfo) -> Result<()> {
+    let dust = rent_exempt_surplus(&pot.to_account_info())?.saturating_sub(pot.get_outstanding());
+    if dust == 0 {
+        return Ok(());
+    }
+
+    pot.record_dust(dust)?;
+    msg!("swept {} lamports of dust from pot {} to {}", dust, pot.key(), to.key());
+
+    transfer_owned_sol(&mut pot.to_account_info(), to, dust)
+}
+
+//refund accounts must be passed in the same order as the refunds, followed by each of the pot's sponsors in the order they first sponsored
+pub fn refund_pot(pot: &mut Account<Pot>, refunds: &[(Pubkey, u64)], refund_accounts: &[AccountInfo]) -> Result<()> {
+    let sponsors = pot.get_sponsors().to_vec();
+    require_eq!(refund_accounts.len(), refunds.len() + sponsors.len(), GameError::RefundAccountsMismatch);
+
+    let sponsor_refunds = sponsors.iter().map(|s| (s.address, s.amount));
+    for ((payee, amount), payee_account_info) in refunds.iter().copied().chain(sponsor_refunds).zip(refund_accounts.iter()) {
+        require_keys_eq!(payee, payee_account_info.key(), GameError::RefundAccountsMismatch);
+        debit_pot(pot, &mut payee_account_info.clone(), amount, PotDebit::Refund)?;
+    }
+
+    pot.require_settled()
+}
+
+//takes the protocol fee, pays the rest out by the game's payout table, then sweeps any dust to the treasury.
+//part of the fee feeds the jackpot, and backers' shares are held back for them to claim.
+//placement accounts must be passed in finishing order, one for each paid position. returns what each placed player was paid
+pub fn pay_placements(game: &mut Game, pot: &mut Account<Pot>, placement_accounts: &[AccountInfo], metadata: &Account<Metadata>, jackpot: &mut Account<Jackpot>) -> Result<Vec<(Pubkey, u64)>> {
+    require_eq!(pot.get_outstanding(), game.get_total_stake() + game.get_total_backing() + pot.sponsored, GameError::PotConservationViolated);
+
+    let total = pot.get_outstanding() as u128;
+    let fee = total * metadata.get_fee_bps() as u128 / MAX_FEE_BPS as u128;
+    let winnings = total - fee;
+    let placements = game.get_placements().to_vec();
+    let payout_table = game.get_payout_table().to_vec();
+    let paid_positions = payout_table.len().min(placements.len());
+    require_gte!(placement_accounts.len(), paid_positions, GameError::PayoutAccountsMismatch);
+
+    //the winner's share is paid last so it picks up rounding and any positions nobody finished in
+    let mut remaining_winnings = winnings;
+    let mut backer_owed = 0;
+    let mut payouts = Vec::with_capacity(paid_positions);
+    for position in (0..paid_positions).rev() {
+        let placement_account_info = &mut placement_accounts[position].clone();
+        require_keys_eq!(placements[position], placement_account_info.key(), GameError::PayoutAccountsMismatch);
+
+        let amount = if position == 0 {
+            remaining_winnings
+        } else {
+            winnings * payout_table[position] as u128 / PAYOUT_TABLE_TOTAL as u128
+        };
+
+        remaining_winnings -= amount;
+        let backer_share = game.split_backer_share(placements[position], amount as u64)?;
+        backer_owed += backer_share;
+        debit_pot(pot, placement_account_info, amount as u64 - backer_share, PotDebit::Payout)?;
+        payouts.push((placements[position], amount as u64 - backer_share));
+    }
+    pot.set_backer_owed(backer_owed);
+
+    let treasury = &mut metadata.to_account_info();
+    let (jackpot_share, treasury_share) = metadata.split_fee(fee as u64);
+    debit_pot(pot, &mut jackpot.to_account_info(), jackpot_share, PotDebit::Fee)?;
+    jackpot.contribute(jackpot_share)?;
+    debit_pot(pot, treasury, treasury_share, PotDebit::Fee)?;
+    pot.require_settled()?;
+
+    //the pot's rent stays behind and is returned to the creator by game_close
+    sweep_pot_dust(pot, treasury)?;
+
+    Ok(payouts)
+}
+
+//splits remaining accounts into the players' profiles, which always come first, and whatever follows them
+pub fn split_profile_accounts<'a, 'info>(game: &Game, accounts: &'a [AccountInfo<'info>]) -> Result<(&'a [AccountInfo<'info>], &'a [AccountInfo<'info>])> {
+    let player_count = game.get_player_count() as usize;
+    require_gte!(accounts.len(), player_count, GameError::ProfileAccountsMismatch);
+
+    Ok(accounts.split_at(player_count))
+}
+
+//profile accounts must be passed in seat order, one for each player
+pub fn record_games_started(game: &Game, profile_accounts: &[AccountInfo], program_id: &Pubkey) -> Result<()> {
+    let (profile_accounts, _) = split_profile_accounts(game, profile_accounts)?;
+
+    for (player, profile_account_info) in game.get_players().iter().zip(profile_accounts.iter()) {
+        let mut profile = Account::<PlayerProfile>::try_from(profile_account_info)?;
+        require_keys_eq!(profile.get_player(), *player, GameError::ProfileAccountsMismatch);
+        profile.record_game_started();
+        profile.exit(program_id)?;
+    }
+
+    Ok(())
+}
+
+//profile accounts must be passed in seat order, one for each player. games without a winner count as ties.
+//a wallet in repeat seats is only recorded once, for everything its seats staked, and isn't rated against itself
+pub fn record_results(game: &Game, profile_accounts: &[AccountInfo], payouts: &[(Pubkey, u64)], k_factor: u16, program_id: &Pubkey) -> Result<()> {
+    let player_stakes = game.get_player_stakes();
+    require_gte!(profile_accounts.len(), player_stakes.len(), GameError::ProfileAccountsMismatch);
+
+    let mut profiles = Vec::with_capacity(player_stakes.len());
+    for ((player, _), profile_account_info) in player_stakes.iter().zip(profile_accounts.iter()) {
+        let profile = Account::<PlayerProfile>::try_from(profile_account_info)?;
+        require_keys_eq!(profile.get_player(), *player, GameError::ProfileAccountsMismatch);
+        profiles.push(profile);
+    }
+
+    //ratings only move in rated head to head games, and both players are rated off their ratings going in
+    let ratings: Vec<u16> = profiles.iter().map(|p| p.get_rating()).collect();
+    let rated = game.is_rated() && ratings.len() == 2 && player_stakes[0].0 != player_stakes[1].0;
+
+    for (seat, ((player, _), profile)) in player_stakes.iter().zip(profiles.iter_mut()).enumerate() {
+        if player_stakes[..seat].iter().any(|(p, _)| p == player) {
+            continue;
+        }
+
+        let result = match game.get_state() {
+            GameState::Won{winner} if winner == *player => GameResult::Win,
+            GameState::Won{..} => GameResult::Loss,
+            _ => GameResult::Tie,
+        };
+        let stake = player_stakes.iter()
+            .filter(|(p, _)| p == player)
+            .map(|(_, stake)| stake)
+            .sum();
+        let won = payouts.iter()
+            .filter(|(payee, _)| payee == player)
+            .map(|(_, amount)| amount)
+            .sum();
+
+        if rated {
+            profile.rate(ratings[1 - seat], &result, k_factor);
+        }
+
+        profile.record_result(result, stake, won)?;
+        profile.exit(program_id)?;
+    }
+
+    Ok(())
+}
+
+//pays everything above the jackpot's rent to the winner, up to max_amount
+pub fn pay_jackpot(jackpot: &mut Account<Jackpot>, winner: &mut AccountInfo, max_amount: u64) -> Result<()> {
+    let jackpot_account_info = &mut jackpot.to_account_info();
+    let amount = rent_exempt_surplus(jackpot_account_info)?.min(max_amount);
+
+    jackpot.record_win(winner.key(), amount)?;
+    msg!("jackpot of {} lamports won by {}", amount, winner.key());
+
+    transfer_owned_sol(jackpot_account_info, winner, amount)
+}
+
+//refunds an unstarted game's stake and sponsorships, then closes its pot to the creator. the pot's sponsors must be
+//passed in the order they first sponsored. returns false if backers still have refunds to claim, in which case the
+//game and pot are left for game_close
+pub fn refund_unstarted_game<'info>(game: &Game, pot: &AccountInfo<'info>, creator: &mut AccountInfo<'info>, sponsor_accounts: &[AccountInfo<'info>], program_id: &Pubkey) -> Result<bool> {
+    if game.is_free_to_play() {
+        return Ok(true);
+    }
+
+    //the second player to join starts the game, so only the creator has a stake to refund
+    let creator_refund = game.get_player_stakes()[0];
+    require_keys_eq!(creator_refund.0, creator.key(), GameError::RefundAccountsMismatch);
+
+    let mut refund_accounts = vec![creator.clone()];
+    refund_accounts.extend_from_slice(sponsor_accounts);
+
+    let pot = &mut load_pot(pot)?;
+    refund_pot(pot, &[creator_refund], &refund_accounts)?;
+
+    if game.get_backer_count() > 0 {
+        pot.exit(program_id)?;
+        return Ok(false);
+    }
+
+    close_pot(pot, creator)?;
+
+    Ok(true)
+}
+
+//once the pot's books are settled, any dust goes along with the rent to the destination
+pub fn close_pot<'info>(pot: &mut Account<'info, Pot>, destination: &mut AccountInfo<'info>) -> Result<()> {
+    pot.require_settled()?;
+    require_eq!(pot.backer_owed, 0, GameError::BackersUnclaimed);
+    sweep_pot_dust(pot, destination)?;
+
+    pot.close(destination.clone())
+}
+
+//moves a player's wager into the game's pot
+pub fn deposit_to_pot<'info>(from: &AccountInfo<'info>, pot: &mut Account<'info, Pot>, amount: u64) -> Result<()> {
+    transfer_sol(from, &pot.to_account_info(), amount)?;
+
+    pot.deposit(amount)
+}
+
+//transfers lamports out of a system owned account, such as a signing player
+pub fn transfer_sol<'info>(from: &AccountInfo<'info>, to: &AccountInfo<'info>, amount: u64) -> Result<()> {
     let ix = anchor_lang::solana_program::system_instruction::transfer(
         from.key,
         to.key,
         amount,
     );
 
-    let result = anchor_lang::solana_program::program::invoke(
+    anchor_lang::solana_program::program::invoke(
         &ix,
         &[from.clone(), to.clone()]
-    );
+    )?;
 
-    match result {
-        Err(_err)=> Err(_err.into()),
-        Ok(()) => Ok(())
-    }
+    Ok(())
+}
+
+pub fn rent_exempt_surplus(account: &AccountInfo) -> Result<u64> {
+    let minimum_balance = Rent::get()?.minimum_balance(account.data_len());
+
+    Ok(account.lamports().saturating_sub(minimum_balance))
+}
+
+//reads an spl token account's balance by its layout: mint 0..32, owner 32..64, amount 64..72, state 108.
+//an uninitialized account's bytes mean nothing, so its state must be initialized or frozen
+pub fn token_balance(token_account: &AccountInfo, owner: &Pubkey, mint: &Pubkey) -> Result<u64> {
+    require_keys_eq!(*token_account.owner, token_program::ID, GameError::InvalidGateTokenAccount);
+
+    let data = token_account.try_borrow_data()?;
+    require_eq!(data.len(), TOKEN_ACCOUNT_LEN, GameError::InvalidGateTokenAccount);
+    require!(data[TOKEN_ACCOUNT_STATE_OFFSET] != TOKEN_ACCOUNT_UNINITIALIZED, GameError::InvalidGateTokenAccount);
+    require!(data[0..32] == mint.to_bytes() && data[32..64] == owner.to_bytes(), GameError::InvalidGateTokenAccount);
+
+    let mut amount = [0u8; 8];
+    amount.copy_from_slice(&data[64..72]);
+
+    Ok(u64::from_le_bytes(amount))
 }
-*/
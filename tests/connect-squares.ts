@@ -4,6 +4,7 @@ import {PublicKey, Keypair, sendAndConfirmTransaction} from "@solana/web3.js";
 import * as web3 from "@solana/web3.js";
 import { ConnectSquares } from '../target/types/connect_squares';
 import chai from 'chai';
+import { createMint, createAccount, mintTo } from '@solana/spl-token';
 import { expect } from 'chai';
 
 
@@ -25,17 +26,34 @@ export type GameInitParameters = {
   minPlayers: number,
   maxPlayers: number,
   wager: number,
+  joinerWager?: number,
+  payoutTable?: number[],
+  rated?: boolean,
+  inviteKey?: PublicKey,
+  opponent?: PublicKey,
+  tokenGate?: { mint: PublicKey, amount: anchor.BN },
+  lobbyTtlSeconds?: number,
 };
 
 export type JoinGameParameters = {
   gamePda: PublicKey,
   potPda: PublicKey,
+  invite?: Keypair,
+  gateTokenAccount?: PublicKey,
 };
 
 export type PlayParameters = {
   gamePda: PublicKey,
   potPda: PublicKey,
   tile: Tile,
+  placements?: PublicKey[],
+};
+
+export type CloseGameParameters = {
+  gamePda: PublicKey,
+  potPda: PublicKey,
+  creator: PublicKey,
+  refundPlayers?: PublicKey[],
 };
 
 export type Tile = {
@@ -52,7 +70,7 @@ export type ExpectedPlayResult = {
 
 async function getGamePda(program: Program<ConnectSquares>, creator: PublicKey, nonce?: number) : Promise<[PublicKey,number,number]> {
 
-  if(!nonce) {
+  if(nonce === undefined) {
     do {
       nonce = Math.floor(Math.random() * Math.pow(2,32));
       const [pda, bump] = PublicKey.findProgramAddressSync(
@@ -93,13 +111,21 @@ async function getMetadataPda(programId: PublicKey){
     ], programId);
 }
 
+async function getJackpotPda(programId: PublicKey){
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("jackpot"),
+    ], programId);
+}
+
 async function initGame(program: Program<ConnectSquares>, player: Keypair, params: GameInitParameters) {
   const tx = await program.methods
-        .gameInit(params.gameNonce, params.rows, params.cols, params.connect, params.minPlayers, params.maxPlayers, params.wager)
+        .gameInit(params.gameNonce, params.rows, params.cols, params.connect, params.minPlayers, params.maxPlayers, params.wager, params.joinerWager ?? params.wager, Buffer.from(params.payoutTable ?? [100]), params.rated ?? false, params.inviteKey ?? null, params.opponent ?? null, params.tokenGate ?? null, params.lobbyTtlSeconds ?? null)
         .accounts({
           creator: player.publicKey,
           game: params.gamePda,
           pot: params.potPda,     
+          lobby: getLobbyPda(program.programId, 0)[0],
         })
         .transaction();
       
@@ -108,17 +134,55 @@ async function initGame(program: Program<ConnectSquares>, player: Keypair, param
   return txConfirmation;
 }
 
+function getLobbyPda(programId: PublicKey, page: number) {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("lobby"),
+      new anchor.BN(page).toArrayLike(Buffer, 'be', 2),
+    ], programId);
+}
+
+function getProfilePda(programId: PublicKey, player: PublicKey) {
+  return anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("profile"),
+      player.toBuffer(),
+    ], programId);
+}
+
+//every seated player's profile, in seat order
+async function getProfileAccounts(program: Program<ConnectSquares>, gamePda: PublicKey) {
+  const game = await program.account.game.fetch(gamePda);
+  return game.players
+    .filter(p => !p.equals(PublicKey.default))
+    .map(p => ({ pubkey: getProfilePda(program.programId, p)[0], isWritable: true, isSigner: false }));
+}
+
 async function joinGame(program: Program<ConnectSquares>, player: Keypair, params: JoinGameParameters) {
+  const game = await program.account.game.fetch(params.gamePda);
+  const remainingAccounts = [];
+  if (params.gateTokenAccount) {
+    remainingAccounts.push({ pubkey: params.gateTokenAccount, isWritable: false, isSigner: false });
+  }
+  if (params.invite) {
+    remainingAccounts.push({ pubkey: params.invite.publicKey, isWritable: false, isSigner: true });
+  }
+
   const tx = await program.methods
   .gameJoin()
   .accounts({
     player: player.publicKey,
     game: params.gamePda,
     pot: params.potPda,  
+    playerProfile: getProfilePda(program.programId, player.publicKey)[0],
+    creatorProfile: getProfilePda(program.programId, game.creator)[0],
+    lobby: getLobbyPda(program.programId, game.lobbyPage)[0],
   })
+  .remainingAccounts(remainingAccounts)
   .transaction();
 
-  const txSignature = await anchor.web3.sendAndConfirmTransaction(program.provider.connection, tx, [player], {commitment: 'finalized'});
+  const signers = params.invite ? [player, params.invite] : [player];
+  const txSignature = await anchor.web3.sendAndConfirmTransaction(program.provider.connection, tx, signers, {commitment: 'finalized'});
   const txConfirmation = await program.provider.connection.confirmTransaction(txSignature,'finalized');
 
   return txConfirmation;
@@ -128,6 +192,7 @@ async function play(program: Program<ConnectSquares>, player: Keypair,  playPara
   
   console.log('marking tile: ', playParams.tile);
   const [metadataPda] = await getMetadataPda(program.programId);
+  const [jackpotPda] = await getJackpotPda(program.programId);
 
   const tx = await program.methods
     .gamePlay(playParams.tile)
@@ -136,7 +201,12 @@ async function play(program: Program<ConnectSquares>, player: Keypair,  playPara
       game: playParams.gamePda,
       pot: playParams.potPda,
       metadata: metadataPda,
+      jackpot: jackpotPda,
     })
+    .remainingAccounts([
+      ...await getProfileAccounts(program, playParams.gamePda),
+      ...(playParams.placements ?? []).map(p => ({ pubkey: p, isWritable: true, isSigner: false })),
+    ])
     .transaction();
 
   const txSignature = await anchor.web3.sendAndConfirmTransaction(program.provider.connection, tx, [player], {commitment: 'finalized'});
@@ -153,6 +223,84 @@ async function play(program: Program<ConnectSquares>, player: Keypair,  playPara
   return txConfirmation;
 }
 
+//on a 3x3 board the player who moves first wins down the first column. returns the winner.
+//placements after the winner are passed with the winning move
+async function playFirstColumnWin(program: Program<ConnectSquares>, keypairs: Keypair[], gamePda: PublicKey, potPda: PublicKey, placements?: PublicKey[]) {
+  const game = await program.account.game.fetch(gamePda);
+  const players = game.players.map(p=>{ return keypairs.find(k => k.publicKey.equals(p)) });
+  let playerIndex = game.currentPlayerIndex;
+  let moves = game.moves;
+  const winner = players[playerIndex];
+  const board = [
+    [null,null,null],
+    [null,null,null],
+    [null,null,null]
+  ];
+  const tiles = [{row: 0, column: 0}, {row: 0, column: 1}, {row: 1, column: 0}, {row: 1, column: 1}, {row: 2, column: 0}];
+
+  for (const [i, tile] of tiles.entries()) {
+    board[tile.row][tile.column] = playerIndex;
+    const won = i == tiles.length - 1;
+    await play(program, players[playerIndex],
+      { gamePda: gamePda, potPda: potPda, tile, placements: won ? placements : undefined },
+      { moves: ++moves, playerIndex: won ? playerIndex : playerIndex ? --playerIndex : ++playerIndex,
+        state: won ? { won: { winner: winner.publicKey }, } : GameState.active,
+        board
+      }
+    );
+  }
+
+  return winner;
+}
+
+//on a 3x3 board the player who moves second wins down the last column while the first player plays around them. returns the winner
+async function playLastColumnWin(program: Program<ConnectSquares>, keypairs: Keypair[], gamePda: PublicKey, potPda: PublicKey) {
+  const game = await program.account.game.fetch(gamePda);
+  const players = game.players.map(p=>{ return keypairs.find(k => k.publicKey.equals(p)) });
+  let playerIndex = game.currentPlayerIndex;
+  let moves = game.moves;
+  const winner = players[playerIndex ? 0 : 1];
+  const board = [
+    [null,null,null],
+    [null,null,null],
+    [null,null,null]
+  ];
+  const tiles = [{row: 0, column: 0}, {row: 0, column: 2}, {row: 1, column: 0}, {row: 1, column: 2}, {row: 2, column: 1}, {row: 2, column: 2}];
+
+  for (const [i, tile] of tiles.entries()) {
+    board[tile.row][tile.column] = playerIndex;
+    const won = i == tiles.length - 1;
+    await play(program, players[playerIndex],
+      { gamePda: gamePda, potPda: potPda, tile },
+      { moves: ++moves, playerIndex: won ? playerIndex : playerIndex ? --playerIndex : ++playerIndex,
+        state: won ? { won: { winner: winner.publicKey }, } : GameState.active,
+        board
+      }
+    );
+  }
+
+  return winner;
+}
+
+async function closeGame(program: Program<ConnectSquares>, payer: Keypair, params: CloseGameParameters) {
+  const tx = await program.methods
+    .gameClose()
+    .accounts({
+      game: params.gamePda,
+      pot: params.potPda,
+      creator: params.creator,
+      metadata: (await getMetadataPda(program.programId))[0],
+      closer: payer.publicKey,
+    })
+    .remainingAccounts((params.refundPlayers ?? []).map(pubkey => ({ pubkey, isWritable: true, isSigner: false })))
+    .transaction();
+
+  const txSignature = await anchor.web3.sendAndConfirmTransaction(program.provider.connection, tx, [payer], {commitment: 'finalized'});
+  const txConfirmation = await program.provider.connection.confirmTransaction(txSignature,'finalized');
+
+  return txConfirmation;
+}
+
 describe('connect-squares', () => {
   // Configure the client to use the local cluster.
   anchor.setProvider(anchor.AnchorProvider.env())
@@ -160,6 +308,8 @@ describe('connect-squares', () => {
   const provider = program.provider as anchor.AnchorProvider;
   const playerOne = anchor.web3.Keypair.generate();
   const playerTwo = anchor.web3.Keypair.generate();
+  const treasuryPayeeOne = anchor.web3.Keypair.generate();
+  const treasuryPayeeTwo = anchor.web3.Keypair.generate();
   const wager = 100000;
 
   
@@ -335,6 +485,28 @@ describe('connect-squares', () => {
 
     const withdrawTx = await program.methods
       .metadataWithdraw(new anchor.BN(lamports))
+      .accounts({
+        authority: playerOne.publicKey,
+        metadata: metadataPda,
+      })
+      .transaction();
+
+    try{
+      const withdrawTxSignature = await anchor.web3.sendAndConfirmTransaction(program.provider.connection, withdrawTx, [playerOne], {commitment: 'finalized'});
+      chai.assert(false, "should've failed but didn't ");
+    } catch(err) {
+      expect(err.logs.join()).to.include('Unauthorized');
+    }
+
+    expect(await provider.connection.getBalance(metadataPda)).is.equal(metadataBalanceAfterFund);
+  });
+
+  it('metadata withdraw fail - would dip into rent-exempt minimum', async() =>{
+    const [metadataPda] = await getMetadataPda(program.programId);
+    const metadataBalance = await provider.connection.getBalance(metadataPda);
+
+    const withdrawTx = await program.methods
+      .metadataWithdraw(new anchor.BN(metadataBalance))
       .accounts({
         authority: program.provider.publicKey,
         metadata: metadataPda,
@@ -344,13 +516,313 @@ describe('connect-squares', () => {
     try{
       const withdrawTxSignature = await program.provider.sendAndConfirm(withdrawTx);
       chai.assert(false, "should've failed but didn't ");
+    } catch(err) {
+      expect(err.logs.join()).to.include('InsufficientFunds');
+    }
+
+    const metadataBalanceAfterWithdraw = await provider.connection.getBalance(metadataPda);
+    expect(metadataBalanceAfterWithdraw).is.equal(metadataBalance);
+  });
+
+  it('metadata withdraw all', async() =>{
+    const [metadataPda] = await getMetadataPda(program.programId);
+    const lamports = 300000;
+
+    const fundMetadataTx = new anchor.web3
+      .Transaction()
+      .add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: program.provider.publicKey,
+          toPubkey: metadataPda,
+          lamports,
+        })
+      );
+    const fundMetadataTxSignature = await program.provider.sendAndConfirm(fundMetadataTx);
+
+    const withdrawTx = await program.methods
+      .metadataWithdrawAll()
+      .accounts({
+        authority: program.provider.publicKey,
+        metadata: metadataPda,
+      })
+      .transaction();
+
+    const withdrawTxSignature = await program.provider.sendAndConfirm(withdrawTx);
+
+    const metadataAccount = await provider.connection.getAccountInfo(metadataPda);
+    const rentExemptMinimum = await provider.connection.getMinimumBalanceForRentExemption(metadataAccount.data.length);
+    expect(metadataAccount.lamports).is.equal(rentExemptMinimum);
+
+    const withdrawAgainTx = await program.methods
+      .metadataWithdrawAll()
+      .accounts({
+        authority: program.provider.publicKey,
+        metadata: metadataPda,
+      })
+      .transaction();
+
+    try{
+      const withdrawAgainTxSignature = await program.provider.sendAndConfirm(withdrawAgainTx);
+      chai.assert(false, "should've failed but didn't ");
+    } catch(err) {
+      expect(err.logs.join()).to.include('NothingToWithdraw');
+    }
+  });
+
+  it('set metadata fee', async() => {
+    const [metadataPda] = await getMetadataPda(program.programId);
+    const setFee = async (feeBps: number) => {
+      const tx = await program.methods
+        .metadataSetFee(feeBps)
+        .accounts({
+          authority: program.provider.publicKey,
+          metadata: metadataPda,
+        })
+        .transaction();
+
+      return program.provider.sendAndConfirm(tx);
+    };
+
+    await setFee(250);
+    let metadata = await program.account.metadata.fetch(metadataPda);
+    expect(metadata.feeBps).to.equal(250);
+
+    try {
+      await setFee(10001);
+      chai.assert(false, "should've failed but didn't ");
+    } catch(err) {
+      expect(err.logs.join()).to.include('FeeTooHigh');
+    }
+
+    // later tests expect winners to collect the whole pot
+    await setFee(0);
+    metadata = await program.account.metadata.fetch(metadataPda);
+    expect(metadata.feeBps).to.equal(0);
+  });
+
+  it('init jackpot', async() => {
+    const [metadataPda] = await getMetadataPda(program.programId);
+    const [jackpotPda, jackpotPdaBump] = await getJackpotPda(program.programId);
+    const tx = await program.methods
+      .jackpotInit()
+      .accounts({
+        authority: program.provider.publicKey,
+        metadata: metadataPda,
+        jackpot: jackpotPda,
+      })
+      .transaction();
+
+    await program.provider.sendAndConfirm(tx);
+
+    const jackpot = await program.account.jackpot.fetch(jackpotPda);
+    expect(jackpot.bump).to.equal(jackpotPdaBump);
+    expect(jackpot.contributed.toNumber()).to.equal(0);
+    expect(jackpot.paidOut.toNumber()).to.equal(0);
+    expect(jackpot.wins).to.equal(0);
+  });
+
+  it('set metadata jackpot', async() => {
+    const [metadataPda] = await getMetadataPda(program.programId);
+    const setJackpot = async (feeShareBps: number, minCells: number, minWager: number, maxWinMultiple: number) => {
+      const tx = await program.methods
+        .metadataSetJackpot(feeShareBps, minCells, minWager, maxWinMultiple)
+        .accounts({
+          authority: program.provider.publicKey,
+          metadata: metadataPda,
+        })
+        .transaction();
+
+      return program.provider.sendAndConfirm(tx);
+    };
+
+    await setJackpot(5000, 42, 1000, 3);
+    let metadata = await program.account.metadata.fetch(metadataPda);
+    expect(metadata.jackpotFeeShareBps).to.equal(5000);
+    expect(metadata.jackpotMinCells).to.equal(42);
+    expect(metadata.jackpotMinWager).to.equal(1000);
+    expect(metadata.jackpotMaxWinMultiple).to.equal(3);
+
+    try {
+      await setJackpot(10001, 42, 1000, 3);
+      chai.assert(false, "should've failed but didn't ");
+    } catch(err) {
+      expect(err.logs.join()).to.include('FeeTooHigh');
+    }
+
+    // later tests expect winners to collect the whole pot
+    await setJackpot(0, 0, 0, 0);
+    metadata = await program.account.metadata.fetch(metadataPda);
+    expect(metadata.jackpotMinCells).to.equal(0);
+  });
+
+  it('set metadata k-factor', async() => {
+    const [metadataPda] = await getMetadataPda(program.programId);
+    const setKFactor = async (kFactor: number) => {
+      const tx = await program.methods
+        .metadataSetKFactor(kFactor)
+        .accounts({
+          authority: program.provider.publicKey,
+          metadata: metadataPda,
+        })
+        .transaction();
+
+      return program.provider.sendAndConfirm(tx);
+    };
+
+    let metadata = await program.account.metadata.fetch(metadataPda);
+    expect(metadata.kFactor).to.equal(32);
+
+    try {
+      await setKFactor(101);
+      chai.assert(false, "should've failed but didn't ");
+    } catch(err) {
+      expect(err.logs.join()).to.include('KFactorTooHigh');
+    }
+
+    await setKFactor(40);
+    metadata = await program.account.metadata.fetch(metadataPda);
+    expect(metadata.kFactor).to.equal(40);
+  });
+
+  it('set metadata payees', async() => {
+    const [metadataPda] = await getMetadataPda(program.programId);
+    const payees = [
+      { address: treasuryPayeeOne.publicKey, weight: 1 },
+      { address: treasuryPayeeTwo.publicKey, weight: 3 },
+    ];
+
+    const tx = await program.methods
+      .metadataSetPayees(payees)
+      .accounts({
+        authority: program.provider.publicKey,
+        metadata: metadataPda,
+      })
+      .transaction();
+
+    const txSignature = await program.provider.sendAndConfirm(tx);
+
+    const metadata = await program.account.metadata.fetch(metadataPda);
+    expect(metadata.payees).to.eql(payees);
+  });
+
+  it('set metadata payees fail - unauthorized', async() => {
+    const [metadataPda] = await getMetadataPda(program.programId);
+    const tx = await program.methods
+      .metadataSetPayees([{ address: playerOne.publicKey, weight: 1 }])
+      .accounts({
+        authority: playerOne.publicKey,
+        metadata: metadataPda,
+      })
+      .transaction();
+
+    try{
+      const txSignature = await anchor.web3.sendAndConfirmTransaction(program.provider.connection, tx, [playerOne], {commitment: 'finalized'});
+      chai.assert(false, "should've failed but didn't ");
     } catch(_err) {
-    }  
+    }
+  });
+
+  it('treasury distribute', async() => {
+    const [metadataPda] = await getMetadataPda(program.programId);
+    const lamports = 4000000;
+
+    const fundMetadataTx = new anchor.web3
+      .Transaction()
+      .add(
+        anchor.web3.SystemProgram.transfer({
+          fromPubkey: program.provider.publicKey,
+          toPubkey: metadataPda,
+          lamports,
+        })
+      );
+    const fundMetadataTxSignature = await program.provider.sendAndConfirm(fundMetadataTx);
+
+    const metadataAccount = await provider.connection.getAccountInfo(metadataPda);
+    const rentExemptMinimum = await provider.connection.getMinimumBalanceForRentExemption(metadataAccount.data.length);
+    const distributable = metadataAccount.lamports - rentExemptMinimum;
+
+    const distributeTx = await program.methods
+      .treasuryDistribute()
+      .accounts({
+        metadata: metadataPda,
+      })
+      .remainingAccounts([
+        { pubkey: treasuryPayeeOne.publicKey, isWritable: true, isSigner: false },
+        { pubkey: treasuryPayeeTwo.publicKey, isWritable: true, isSigner: false },
+      ])
+      .transaction();
+
+    const distributeTxSignature = await program.provider.sendAndConfirm(distributeTx);
+
+    const payeeOneBalance = await provider.connection.getBalance(treasuryPayeeOne.publicKey);
+    const payeeTwoBalance = await provider.connection.getBalance(treasuryPayeeTwo.publicKey);
+    expect(payeeOneBalance).to.equal(Math.floor(distributable / 4));
+    expect(payeeTwoBalance).to.equal(Math.floor(distributable * 3 / 4));
+
+    const metadataBalance = await provider.connection.getBalance(metadataPda);
+    expect(metadataBalance).to.be.greaterThanOrEqual(rentExemptMinimum);
+  });
+
+  it('treasury distribute fail - payee accounts mismatch', async() => {
+    const [metadataPda] = await getMetadataPda(program.programId);
+    const distributeTx = await program.methods
+      .treasuryDistribute()
+      .accounts({
+        metadata: metadataPda,
+      })
+      .remainingAccounts([
+        { pubkey: treasuryPayeeTwo.publicKey, isWritable: true, isSigner: false },
+        { pubkey: treasuryPayeeOne.publicKey, isWritable: true, isSigner: false },
+      ])
+      .transaction();
 
+    try{
+      const distributeTxSignature = await program.provider.sendAndConfirm(distributeTx);
+      chai.assert(false, "should've failed but didn't ");
+    } catch(_err) {
+    }
   });
 
 
   
+  it('init player profiles', async() => {
+    for (const player of [playerOne, playerTwo]) {
+      const [profilePda, profilePdaBump] = getProfilePda(program.programId, player.publicKey);
+      const tx = await program.methods
+        .profileInit(player.publicKey)
+        .accounts({
+          payer: program.provider.publicKey,
+          profile: profilePda,
+        })
+        .transaction();
+
+      await program.provider.sendAndConfirm(tx);
+
+      const profile = await program.account.playerProfile.fetch(profilePda);
+      expect(profile.bump).to.equal(profilePdaBump);
+      expect(profile.player).to.eql(player.publicKey);
+      expect(profile.gamesPlayed).to.equal(0);
+    }
+  });
+
+  it('init lobby page', async() => {
+    const [lobbyPda, lobbyPdaBump] = getLobbyPda(program.programId, 0);
+    const tx = await program.methods
+      .lobbyInit(0)
+      .accounts({
+        payer: program.provider.publicKey,
+        lobby: lobbyPda,
+      })
+      .transaction();
+
+    await program.provider.sendAndConfirm(tx);
+
+    const lobby = await program.account.lobby.fetch(lobbyPda);
+    expect(lobby.bump).to.equal(lobbyPdaBump);
+    expect(lobby.page).to.equal(0);
+    expect(lobby.entries).to.be.empty;
+  });
+
   it('setup game', async() => {
     const rows = 3;
     const cols = 3;
@@ -382,6 +854,7 @@ describe('connect-squares', () => {
     expect(game.creator).to.eql(playerOne.publicKey);
     expect(game.currentPlayerIndex).to.equal(0);
     expect(game.initTimestamp.toNumber()).to.be.greaterThan(0);
+    expect(game.expiresAt.toNumber()).to.be.greaterThan(game.initTimestamp.toNumber());
     expect(game.lastMoveSlot.toNumber()).to.equal(0);
     expect(game.maxPlayers).to.equal(maxPlayers);
     expect(game.minPlayers).to.equal(minPlayers);
@@ -391,41 +864,50 @@ describe('connect-squares', () => {
     expect(game.board)
       .to
       .eql([[null,null,null],[null,null,null],[null,null,null]]);
+    expect(Array.from(game.payoutTable)).to.eql([100]);
+    expect(game.placements).to.eql([]);
 
     let pot = await program.account.pot.fetch(potPda);
     expect(pot.bump).to.equal(potPdaBump);
     expect(pot.game).to.eql(gamePda);
+    expect(pot.deposited.toNumber()).to.equal(wager);
+    expect(pot.paidOut.toNumber()).to.equal(0);
+    expect(pot.fees.toNumber()).to.equal(0);
+    expect(pot.refunded.toNumber()).to.equal(0);
   });
 
-  it('join game', async () => {
+  it('setup game fail - payout table has more entries than seats', async() => {
     const rows = 3;
     const cols = 3;
     const connect = 3;
     const minPlayers = 2;
     const maxPlayers = 2;
+
     const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
     const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
 
-    const initGameConfirmation = await initGame(program, playerOne, {
-      gameNonce,
-      gamePda: gamePda,
-      potPda: potPda,
-      cols: cols,
-      rows,
-      connect,
-      minPlayers,
-      maxPlayers,
-      wager,
-    });
-    
-    const confirmation = await joinGame(program, playerTwo, {gamePda, potPda});   
+    try {
+      const confirmation = await initGame(program, playerOne, {
+        gameNonce:gameNonce,
+        gamePda: gamePda,
+        potPda: potPda,
+        cols: cols,
+        rows,
+        connect,
+        minPlayers,
+        maxPlayers,
+        wager,
+        payoutTable: [50, 30, 20],
+      });
+      chai.assert(false, "should've failed but didn't ");
+    } catch(err) {
+      expect(err.logs.join()).to.include('PayoutTableLengthInvalid');
+    }
 
-    let game = await program.account.game.fetch(gamePda);
-    expect(game.state).to.eql({ active:{} });
-    expect(game.players).to.have.deep.members([playerOne.publicKey, playerTwo.publicKey]);
+    expect(await program.account.game.fetchNullable(gamePda)).to.be.null;
   });
 
-  it('cancel game', async() => {
+  it('setup game fail - payout table does not add up to 100', async() => {
     const rows = 3;
     const cols = 3;
     const connect = 3;
@@ -435,53 +917,100 @@ describe('connect-squares', () => {
     const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
     const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
 
-    const confirmation = await initGame(program, playerOne, {
-      gameNonce:gameNonce,
-      gamePda: gamePda,
-      potPda: potPda,
-      cols: cols,
-      rows,
-      connect,
-      minPlayers,
-      maxPlayers,
+    try {
+      const confirmation = await initGame(program, playerOne, {
+        gameNonce:gameNonce,
+        gamePda: gamePda,
+        potPda: potPda,
+        cols: cols,
+        rows,
+        connect,
+        minPlayers,
+        maxPlayers,
+        wager,
+        payoutTable: [60, 30],
+      });
+      chai.assert(false, "should've failed but didn't ");
+    } catch(err) {
+      expect(err.logs.join()).to.include('PayoutTableTotalInvalid');
+    }
+
+    expect(await program.account.game.fetchNullable(gamePda)).to.be.null;
+  });
+
+  it('setup games with the next nonce', async() => {
+    const [profilePda] = getProfilePda(program.programId, playerTwo.publicKey);
+
+    // counted games are derived under their own prefix, so an explicit game can take the counter's next nonce too
+    const [explicitGamePda, explicitGamePdaBump, explicitGameNonce] = await getGamePda(program, playerTwo.publicKey, 0);
+    const [explicitPotPda] = await getPotPda(program.programId, explicitGamePda);
+    await initGame(program, playerTwo, {
+      gameNonce: explicitGameNonce,
+      gamePda: explicitGamePda,
+      potPda: explicitPotPda,
+      cols: 3,
+      rows: 3,
+      connect: 3,
+      minPlayers: 2,
+      maxPlayers: 2,
       wager,
     });
-  
-    const balanceAfterInit = await program.provider.connection.getBalance(playerOne.publicKey);
 
-    let game = await program.account.game.fetch(gamePda);
-    expect(game.state).to.eql({ waiting:{} });
+    for (const expectedCount of [0, 1]) {
+      const profile = await program.account.playerProfile.fetch(profilePda);
+      expect(profile.nextNonce).to.equal(expectedCount);
+
+      const [gamePda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("counted-game"), playerTwo.publicKey.toBuffer(), new anchor.BN(expectedCount).toArrayLike(Buffer, 'be', 4)],
+        program.programId
+      );
+      const [potPda] = await getPotPda(program.programId, gamePda);
+
+      const tx = await program.methods
+        .gameInitNext(3, 3, 3, 2, 2, wager, wager, Buffer.from([100]), false, null, null, null, null)
+        .accounts({
+          creator: playerTwo.publicKey,
+          creatorProfile: profilePda,
+          game: gamePda,
+          pot: potPda,
+          lobby: getLobbyPda(program.programId, 0)[0],
+        })
+        .transaction();
+
+      await anchor.web3.sendAndConfirmTransaction(program.provider.connection, tx, [playerTwo]);
+
+      const game = await program.account.game.fetch(gamePda);
+      expect(game.nonce).to.equal(expectedCount);
+      expect(game.counted).to.be.true;
+      expect(game.creator).to.eql(playerTwo.publicKey);
+    }
+
+    const profile = await program.account.playerProfile.fetch(profilePda);
+    expect(profile.nextNonce).to.equal(2);
+
+    const explicitGame = await program.account.game.fetch(explicitGamePda);
+    expect(explicitGame.nonce).to.equal(0);
+    expect(explicitGame.counted).to.be.false;
 
     const cancelTx = await program.methods
       .gameCancel()
       .accounts({
-        player: playerOne.publicKey,
-        game: gamePda,
-        pot: potPda,
+        player: playerTwo.publicKey,
+        game: explicitGamePda,
+        pot: explicitPotPda,
+        lobby: getLobbyPda(program.programId, 0)[0],
       })
       .transaction();
-
-    const txSignature = await anchor.web3.sendAndConfirmTransaction(program.provider.connection, cancelTx, [playerOne], {commitment: 'finalized'});
-    const txConfirmation = await program.provider.connection.confirmTransaction(txSignature,'finalized');
-
-    const balanceAfterCancel = await program.provider.connection.getBalance(playerOne.publicKey);
-    expect(balanceAfterCancel).to.be.greaterThan(balanceAfterInit);
-
-    let updatedGame = await program.account.game.fetchNullable(gamePda);
-    expect(updatedGame).to.be.null;
-    let updatedPot = await program.account.pot.fetchNullable(potPda);
-    expect(updatedPot).to.be.null;   
+    await anchor.web3.sendAndConfirmTransaction(program.provider.connection, cancelTx, [playerTwo], {commitment: 'finalized'});
+    expect(await program.account.game.fetchNullable(explicitGamePda)).to.be.null;
   });
 
-
-  it('horizontal win!', async () => {
+  it('join game', async () => {
     const rows = 3;
     const cols = 3;
     const connect = 3;
     const minPlayers = 2;
     const maxPlayers = 2;
-    let moves = 0;
-
     const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
     const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
 
@@ -496,147 +1025,247 @@ describe('connect-squares', () => {
       maxPlayers,
       wager,
     });
+    
+    const [playerOneProfilePda] = getProfilePda(program.programId, playerOne.publicKey);
+    const [playerTwoProfilePda] = getProfilePda(program.programId, playerTwo.publicKey);
+    const playerOneGamesPlayed = (await program.account.playerProfile.fetch(playerOneProfilePda)).gamesPlayed;
+    const playerTwoGamesPlayed = (await program.account.playerProfile.fetch(playerTwoProfilePda)).gamesPlayed;
+
+    const confirmation = await joinGame(program, playerTwo, {gamePda, potPda});   
+
+    expect((await program.account.playerProfile.fetch(playerOneProfilePda)).gamesPlayed).to.equal(playerOneGamesPlayed + 1);
+    expect((await program.account.playerProfile.fetch(playerTwoProfilePda)).gamesPlayed).to.equal(playerTwoGamesPlayed + 1);
+
+    let game = await program.account.game.fetch(gamePda);
+    expect(game.state).to.eql({ active:{} });
+    expect(game.players).to.have.deep.members([playerOne.publicKey, playerTwo.publicKey]);
+
+    let pot = await program.account.pot.fetch(potPda);
+    expect(pot.deposited.toNumber()).to.equal(wager * 2);
+  });
+
+  it('join private game with invite key', async () => {
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+    const invite = Keypair.generate();
+
+    await initGame(program, playerOne, {
+      gameNonce,
+      gamePda: gamePda,
+      potPda: potPda,
+      cols: 3,
+      rows: 3,
+      connect: 3,
+      minPlayers: 2,
+      maxPlayers: 2,
+      wager,
+      inviteKey: invite.publicKey,
+    });
+
+    const lobby = await program.account.lobby.fetch(getLobbyPda(program.programId, 0)[0]);
+    expect(lobby.entries.map(e => e.game.toBase58())).to.not.include(gamePda.toBase58());
+
+    for (const wrongInvite of [undefined, Keypair.generate()]) {
+      try {
+        await joinGame(program, playerTwo, {gamePda, potPda, invite: wrongInvite});
+        chai.assert(false, "should've failed but didn't ");
+      } catch(err) {
+        expect(err.logs.join()).to.include('InviteRequired');
+      }
+    }
+
+    await joinGame(program, playerTwo, {gamePda, potPda, invite});
 
-    const joinGameConfirmation = await joinGame(program, playerTwo, { gamePda, potPda});
     const game = await program.account.game.fetch(gamePda);
-    const players = game.players.map(p=>{ return p.equals(playerOne.publicKey) ? playerOne : playerTwo });
-    let playerIndex = game.currentPlayerIndex;
+    expect(game.state).to.eql({ active:{} });
+    expect(game.players).to.have.deep.members([playerOne.publicKey, playerTwo.publicKey]);
+  });
 
-    await play(program, players[playerIndex],
-        { gamePda: gamePda, potPda: potPda, tile: {row: 0, column: 0}},
-        { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
-          board: [
-            [0,null,null],
-            [null,null,null],
-            [null,null,null]
-          ]
-        }    
-    );
+  it('decline a direct challenge', async () => {
+    const opponent = treasuryPayeeOne;
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
 
-    try {
-      await play(program, 
-        // same player in subsequent turns
-        // change sth about the tx because
-        // duplicate tx that come in too fast
-        // after each other may get dropped
-        players[playerIndex-1],
-        { gamePda: gamePda, potPda: potPda, tile: {row: 1, column: 0}},
-        { moves: moves, playerIndex: playerIndex, state: GameState.active, 
-          board: [
-            [0,null,null],
-            [null,null,null],
-            [null,null,null]
-          ]
-        }    
-      );
+    await initGame(program, playerOne, {
+      gameNonce,
+      gamePda: gamePda,
+      potPda: potPda,
+      cols: 3,
+      rows: 3,
+      connect: 3,
+      minPlayers: 2,
+      maxPlayers: 2,
+      wager,
+      opponent: opponent.publicKey,
+    });
+
+    const balanceAfterInit = await program.provider.connection.getBalance(playerOne.publicKey);
+    const lobby = await program.account.lobby.fetch(getLobbyPda(program.programId, 0)[0]);
+    expect(lobby.entries.map(e => e.game.toBase58())).to.not.include(gamePda.toBase58());
 
+    try {
+      await joinGame(program, playerTwo, {gamePda, potPda});
       chai.assert(false, "should've failed but didn't ");
-    } catch (_err) {
+    } catch(err) {
+      expect(err.logs.join()).to.include('NotChallenged');
     }
 
-    await play(program, players[playerIndex],
-      { gamePda: gamePda, potPda: potPda, tile: {row: 1, column: 0}},
-      { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
-        board: [
-          [0,null,null],
-          [1,null,null],
-          [null,null,null]
-        ]
-      }    
-    );
+    const tx = await program.methods
+      .gameDecline()
+      .accounts({
+        game: gamePda,
+        pot: potPda,
+        opponent: opponent.publicKey,
+        creator: playerOne.publicKey,
+      })
+      .transaction();
 
-    await play(program, players[playerIndex],
-      { gamePda: gamePda, potPda: potPda, tile: {row: 0, column: 1}},
-      { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
-        board: [
-          [0,0,null],
-          [1,null,null],
-          [null,null,null]
-        ]
-      }    
-    );
+    await program.provider.sendAndConfirm(tx, [opponent]);
 
-    try {
-      await play(program, players[playerIndex-1],
-        { gamePda: gamePda, potPda: potPda, tile: {row: 5, column: 1}}, // out of bounds row
-        { moves: moves, playerIndex: playerIndex, state: GameState.active, 
-          board: [
-            [0,0,null],
-            [1,null,null],
-            [null,null,null]
-          ]
-        }    
-      );
+    expect(await program.account.game.fetchNullable(gamePda)).to.be.null;
+    expect(await program.account.pot.fetchNullable(potPda)).to.be.null;
+    const balanceAfterDecline = await program.provider.connection.getBalance(playerOne.publicKey);
+    expect(balanceAfterDecline).to.be.greaterThan(balanceAfterInit + wager);
+  });
+
+  it('join token-gated game', async () => {
+    const mint = await createMint(program.provider.connection, playerOne, playerOne.publicKey, null, 0);
+    const tokenAccount = await createAccount(program.provider.connection, playerTwo, mint, playerTwo.publicKey);
+    await mintTo(program.provider.connection, playerOne, mint, tokenAccount, playerOne, 4);
+
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
 
+    await initGame(program, playerOne, {
+      gameNonce,
+      gamePda: gamePda,
+      potPda: potPda,
+      cols: 3,
+      rows: 3,
+      connect: 3,
+      minPlayers: 2,
+      maxPlayers: 2,
+      wager,
+      tokenGate: { mint, amount: new anchor.BN(5) },
+    });
+
+    try {
+      await joinGame(program, playerTwo, {gamePda, potPda, gateTokenAccount: tokenAccount});
       chai.assert(false, "should've failed but didn't ");
-    } catch (_err) {
+    } catch(err) {
+      expect(err.logs.join()).to.include('InsufficientGateBalance');
     }
 
-    await play(program, players[playerIndex],
-      { gamePda: gamePda, potPda: potPda, tile: {row: 1, column: 1}},
-      { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
-        board: [
-          [0,0,null],
-          [1,1,null],
-          [null,null,null]
-        ]
-      }    
-    );
+    await mintTo(program.provider.connection, playerOne, mint, tokenAccount, playerOne, 1);
+    await joinGame(program, playerTwo, {gamePda, potPda, gateTokenAccount: tokenAccount});
+
+    const game = await program.account.game.fetch(gamePda);
+    expect(game.state).to.eql({ active:{} });
+    expect(game.tokenGate.mint).to.eql(mint);
+  });
+
+  it('join game fail - already joined, unless the authority allows repeat seats', async () => {
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+    const [metadataPda] = await getMetadataPda(program.programId);
+    const [profilePda] = getProfilePda(program.programId, playerOne.publicKey);
+
+    await initGame(program, playerOne, {
+      gameNonce,
+      gamePda: gamePda,
+      potPda: potPda,
+      cols: 3,
+      rows: 3,
+      connect: 3,
+      minPlayers: 2,
+      maxPlayers: 2,
+      wager,
+    });
 
     try {
-      await play(program, players[playerIndex],
-        { gamePda: gamePda, potPda: potPda, tile: {row: 1, column: 1}}, //should cause tile already set error
-        { moves: moves, playerIndex: playerIndex, state: GameState.active, 
-          board: [
-            [0,0,null],
-            [1,1,null],
-            [null,null,null]
-          ]
-        }    
-      );
+      await joinGame(program, playerOne, {gamePda, potPda});
+      chai.assert(false, "should've failed but didn't ");
+    } catch(err) {
+      expect(err.logs.join()).to.include('PlayerAlreadyJoined');
+    }
+
+    const tx = await program.methods
+      .gameSetRepeatSeats(true)
+      .accounts({
+        authority: program.provider.publicKey,
+        metadata: metadataPda,
+        game: gamePda,
+      })
+      .transaction();
+
+    await program.provider.sendAndConfirm(tx);
+
+    // the creator takes the second seat, which starts the game and counts it once on their profile
+    const profileBeforeJoin = await program.account.playerProfile.fetch(profilePda);
+    await joinGame(program, playerOne, {gamePda, potPda});
+
+    let game = await program.account.game.fetch(gamePda);
+    expect(game.state).to.eql({ active:{} });
+    expect(game.players).to.eql([playerOne.publicKey, playerOne.publicKey]);
+    expect((await program.account.playerProfile.fetch(profilePda)).gamesPlayed).to.equal(profileBeforeJoin.gamesPlayed + 1);
 
+    // doubling prices each seat by its wallet, so it isn't available with repeat seats
+    try {
+      const doubleTx = await program.methods
+        .gameDoubleOffer()
+        .accounts({
+          game: gamePda,
+          pot: potPda,
+          player: playerOne.publicKey,
+        })
+        .transaction();
+      await anchor.web3.sendAndConfirmTransaction(program.provider.connection, doubleTx, [playerOne], {commitment: 'finalized'});
       chai.assert(false, "should've failed but didn't ");
-    } catch (_err) {
+    } catch(err) {
+      expect(err.logs.join()).to.include('DoublingNotAvailable');
     }
 
-    await play(program, players[playerIndex],
-      { gamePda: gamePda, potPda: potPda, tile: {row: 0, column: 2}},
-      { moves: ++moves, playerIndex: playerIndex, state: { won: { winner: players[playerIndex].publicKey }, }, 
-        board: [
-          [0,0,0],
-          [1,1,null],
-          [null,null,null]
-        ]
-      }    
-    );
- 
+    const potBalanceBeforeWin = await provider.connection.getBalance(potPda);
+    const winner = await playFirstColumnWin(program, [playerOne], gamePda, potPda);
+    expect(winner).to.equal(playerOne);
+    expect(await provider.connection.getBalance(potPda)).to.equal(potBalanceBeforeWin - wager * 2);
 
+    // the wallet's result is recorded once, for both seats' stakes
+    const profileAfterWin = await program.account.playerProfile.fetch(profilePda);
+    expect(profileAfterWin.wins).to.equal(profileBeforeJoin.wins + 1);
+    expect(profileAfterWin.losses).to.equal(profileBeforeJoin.losses);
+    expect(profileAfterWin.totalWagered.toNumber()).to.equal(profileBeforeJoin.totalWagered.toNumber() + wager * 2);
+    expect(profileAfterWin.totalWon.toNumber()).to.equal(profileBeforeJoin.totalWon.toNumber() + wager * 2);
+    expect(profileAfterWin.rating).to.equal(profileBeforeJoin.rating);
+
+    // rematch votes are kept per wallet, so they can't tell the seats apart either
     try {
-      await play(program, players[playerIndex],
-        { gamePda: gamePda, potPda: potPda, tile: {row: 0, column: 2}}, //should throw an error that the game has already been won
-        { moves: moves, playerIndex: playerIndex, state: { won: { winner: players[playerIndex].publicKey }, }, 
-          board: [
-            [0,0,0],
-            [1,1,null],
-            [null,null,null]
-          ]
-        }    
-      );
-      
+      const rematchTx = await program.methods
+        .gameRematch()
+        .accounts({
+          game: gamePda,
+          pot: potPda,
+          player: playerOne.publicKey,
+        })
+        .remainingAccounts(await getProfileAccounts(program, gamePda))
+        .transaction();
+      await anchor.web3.sendAndConfirmTransaction(program.provider.connection, rematchTx, [playerOne]);
       chai.assert(false, "should've failed but didn't ");
-    } catch (_err) {
+    } catch(err) {
+      expect(err.logs.join()).to.include('RematchNotAvailable');
     }
-    
+
+    await closeGame(program, playerOne, { gamePda, potPda, creator: playerOne.publicKey });
+    expect(await program.account.game.fetchNullable(gamePda)).to.be.null;
   });
 
-  it('vertical win!', async () => {
+  it('join game with odds', async () => {
     const rows = 3;
     const cols = 3;
     const connect = 3;
     const minPlayers = 2;
     const maxPlayers = 2;
-    let moves = 0;
-
+    const creatorWager = wager * 2;
     const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
     const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
 
@@ -649,27 +1278,1176 @@ describe('connect-squares', () => {
       connect,
       minPlayers,
       maxPlayers,
-      wager,
+      wager: creatorWager,
+      joinerWager: wager,
     });
 
-    const joinGameConfirmation = await joinGame(program, playerTwo, { gamePda, potPda}); 
-    const game = await program.account.game.fetch(gamePda);
-    const players = game.players.map(p=>{ return p.equals(playerOne.publicKey) ? playerOne : playerTwo });
-    let playerIndex = game.currentPlayerIndex;
+    let game = await program.account.game.fetch(gamePda);
+    expect(game.wager).to.equal(creatorWager);
+    expect(game.joinerWager).to.equal(wager);
 
-    await play(program, players[playerIndex],
-        { gamePda: gamePda, potPda: potPda, tile: {row: 0, column: 0}},
-        { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
-          board: [
-            [0,null,null],
-            [null,null,null],
-            [null,null,null]
-          ]
-        }    
-    );    
-    
-    await play(program, players[playerIndex],
-      { gamePda: gamePda, potPda: potPda, tile: {row: 0, column: 1}},
+    const joinerBalanceBeforeJoin = await provider.connection.getBalance(playerTwo.publicKey);
+    const confirmation = await joinGame(program, playerTwo, {gamePda, potPda});
+
+    // the joiner also pays the transaction fee
+    const joinerBalanceAfterJoin = await provider.connection.getBalance(playerTwo.publicKey);
+    expect(joinerBalanceBeforeJoin - joinerBalanceAfterJoin).to.be.lessThan(creatorWager);
+
+    let pot = await program.account.pot.fetch(potPda);
+    expect(pot.deposited.toNumber()).to.equal(creatorWager + wager);
+
+    const fetchProfile = (player: Keypair) => program.account.playerProfile.fetch(getProfilePda(program.programId, player.publicKey)[0]);
+    const profilesBeforeWin = [await fetchProfile(playerOne), await fetchProfile(playerTwo)];
+
+    // the winner takes the whole pot whichever side of the odds they were on
+    const potBalanceBeforeWin = await provider.connection.getBalance(potPda);
+    const winner = await playFirstColumnWin(program, [playerOne, playerTwo], gamePda, potPda);
+    expect(await provider.connection.getBalance(potPda)).to.equal(potBalanceBeforeWin - creatorWager - wager);
+
+    for (const [i, player] of [playerOne, playerTwo].entries()) {
+      const profile = await fetchProfile(player);
+      const won = player === winner;
+      expect(profile.wins).to.equal(profilesBeforeWin[i].wins + (won ? 1 : 0));
+      expect(profile.losses).to.equal(profilesBeforeWin[i].losses + (won ? 0 : 1));
+      expect(profile.totalWagered.toNumber()).to.equal(profilesBeforeWin[i].totalWagered.toNumber() + (player === playerOne ? creatorWager : wager));
+      expect(profile.totalWon.toNumber()).to.equal(profilesBeforeWin[i].totalWon.toNumber() + (won ? creatorWager + wager : 0));
+    }
+
+    await closeGame(program, playerTwo, { gamePda, potPda, creator: playerOne.publicKey });
+  });
+
+  it('rated game moves ratings', async () => {
+    const rows = 3;
+    const cols = 3;
+    const connect = 3;
+    const minPlayers = 2;
+    const maxPlayers = 2;
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+    const fetchProfile = (player: Keypair) => program.account.playerProfile.fetch(getProfilePda(program.programId, player.publicKey)[0]);
+
+    const initGameConfirmation = await initGame(program, playerOne, {
+      gameNonce,
+      gamePda: gamePda,
+      potPda: potPda,
+      cols: cols,
+      rows,
+      connect,
+      minPlayers,
+      maxPlayers,
+      wager,
+      rated: true,
+    });
+
+    let game = await program.account.game.fetch(gamePda);
+    expect(game.rated).to.be.true;
+
+    const joinGameConfirmation = await joinGame(program, playerTwo, { gamePda, potPda});
+    const ratingsBefore = [(await fetchProfile(playerOne)).rating, (await fetchProfile(playerTwo)).rating];
+
+    const winner = await playFirstColumnWin(program, [playerOne, playerTwo], gamePda, potPda);
+    const ratingsAfter = [(await fetchProfile(playerOne)).rating, (await fetchProfile(playerTwo)).rating];
+
+    // elo is zero sum between two players
+    const winnerSeat = winner === playerOne ? 0 : 1;
+    expect(ratingsAfter[winnerSeat]).to.be.greaterThan(ratingsBefore[winnerSeat]);
+    expect(ratingsAfter[1 - winnerSeat]).to.be.lessThan(ratingsBefore[1 - winnerSeat]);
+    expect(ratingsAfter[0] + ratingsAfter[1]).to.equal(ratingsBefore[0] + ratingsBefore[1]);
+
+    await closeGame(program, playerTwo, { gamePda, potPda, creator: playerOne.publicKey });
+  });
+
+  it('match make from a rated queue', async () => {
+    const rows = 3;
+    const cols = 3;
+    const connect = 3;
+    const turnSlots = 120;
+    const presetSeed = Buffer.concat([Buffer.from([rows, cols, connect, turnSlots]), new anchor.BN(wager).toArrayLike(Buffer, 'be', 4)]);
+    const [queuePda] = anchor.web3.PublicKey.findProgramAddressSync([anchor.utils.bytes.utf8.encode("queue"), presetSeed], program.programId);
+
+    const queueInitTx = await program.methods
+      .queueInit(rows, cols, connect, wager, turnSlots)
+      .accounts({
+        payer: program.provider.publicKey,
+        queue: queuePda,
+      })
+      .transaction();
+
+    await program.provider.sendAndConfirm(queueInitTx);
+
+    const enqueue = async (player: Keypair) => {
+      const tx = await program.methods
+        .queueEnqueue()
+        .accounts({
+          queue: queuePda,
+          profile: getProfilePda(program.programId, player.publicKey)[0],
+          player: player.publicKey,
+        })
+        .transaction();
+
+      return anchor.web3.sendAndConfirmTransaction(program.provider.connection, tx, [player], {commitment: 'finalized'});
+    };
+
+    await enqueue(playerOne);
+    try {
+      await enqueue(playerOne);
+      chai.assert(false, "should've failed but didn't ");
+    } catch(_err) {
+    }
+    await enqueue(playerTwo);
+
+    let queue = await program.account.matchQueue.fetch(queuePda);
+    expect(queue.entries.map(e => e.player)).to.eql([playerOne.publicKey, playerTwo.publicKey]);
+
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, program.provider.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+    const matchTx = await program.methods
+      .matchMake(gameNonce, 0, 1)
+      .accounts({
+        queue: queuePda,
+        game: gamePda,
+        pot: potPda,
+        firstProfile: getProfilePda(program.programId, playerOne.publicKey)[0],
+        secondProfile: getProfilePda(program.programId, playerTwo.publicKey)[0],
+        payer: program.provider.publicKey,
+      })
+      .transaction();
+
+    await program.provider.sendAndConfirm(matchTx);
+
+    queue = await program.account.matchQueue.fetch(queuePda);
+    expect(queue.entries).to.be.empty;
+    expect(queue.gamesMatched).to.equal(1);
+
+    const game = await program.account.game.fetch(gamePda);
+    expect(game.state).to.eql({ active:{} });
+    expect(game.rated).to.be.true;
+    expect(game.turnSlots).to.equal(turnSlots);
+    expect(game.players).to.have.deep.members([playerOne.publicKey, playerTwo.publicKey]);
+
+    const pot = await program.account.pot.fetch(potPda);
+    expect(pot.deposited.toNumber()).to.equal(wager * 2);
+
+    await playFirstColumnWin(program, [playerOne, playerTwo], gamePda, potPda);
+    await closeGame(program, playerTwo, { gamePda, potPda, creator: program.provider.publicKey });
+    expect(await program.account.game.fetchNullable(gamePda)).to.be.null;
+  });
+
+  it('timed out game is refunded and recorded as a tie', async () => {
+    const rows = 3;
+    const cols = 3;
+    const connect = 3;
+    const turnSlots = 1;
+    const presetSeed = Buffer.concat([Buffer.from([rows, cols, connect, turnSlots]), new anchor.BN(wager).toArrayLike(Buffer, 'be', 4)]);
+    const [queuePda] = anchor.web3.PublicKey.findProgramAddressSync([anchor.utils.bytes.utf8.encode("queue"), presetSeed], program.programId);
+
+    const queueInitTx = await program.methods
+      .queueInit(rows, cols, connect, wager, turnSlots)
+      .accounts({
+        payer: program.provider.publicKey,
+        queue: queuePda,
+      })
+      .transaction();
+
+    await program.provider.sendAndConfirm(queueInitTx);
+
+    for (const player of [playerOne, playerTwo]) {
+      const tx = await program.methods
+        .queueEnqueue()
+        .accounts({
+          queue: queuePda,
+          profile: getProfilePda(program.programId, player.publicKey)[0],
+          player: player.publicKey,
+        })
+        .transaction();
+
+      await anchor.web3.sendAndConfirmTransaction(program.provider.connection, tx, [player], {commitment: 'finalized'});
+    }
+
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, program.provider.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+    const matchTx = await program.methods
+      .matchMake(gameNonce, 0, 1)
+      .accounts({
+        queue: queuePda,
+        game: gamePda,
+        pot: potPda,
+        firstProfile: getProfilePda(program.programId, playerOne.publicKey)[0],
+        secondProfile: getProfilePda(program.programId, playerTwo.publicKey)[0],
+        payer: program.provider.publicKey,
+      })
+      .transaction();
+
+    await program.provider.sendAndConfirm(matchTx);
+
+    //nobody moves, so the game times out once GAME_TIMEOUT_TURNS turns have passed
+    const game = await program.account.game.fetch(gamePda);
+    expect(game.turnSlots).to.equal(turnSlots);
+    const connection = program.provider.connection;
+    while (await connection.getSlot() < game.lastMoveSlot.toNumber() + turnSlots * 100) {
+      await new Promise(resolve => setTimeout(resolve, 500));
+    }
+
+    const currentPlayer = [playerOne, playerTwo].find(p => p.publicKey.equals(game.players[game.currentPlayerIndex]));
+    try {
+      await play(program, currentPlayer, { gamePda, potPda, tile: {row: 0, column: 0} }, { moves: 1, playerIndex: 0, state: GameState.active, board: [] });
+      chai.assert(false, "should've failed but didn't ");
+    } catch(err) {
+      expect(err.logs.join()).to.include('GameTimedOut');
+    }
+
+    const profilePdas = game.players.map(p => getProfilePda(program.programId, p)[0]);
+    const profilesBefore = await Promise.all(profilePdas.map(p => program.account.playerProfile.fetch(p)));
+    const balancesBefore = await Promise.all(game.players.map(p => connection.getBalance(p)));
+
+    // profiles then players in seat order, the provider closes so the players pay no fees
+    const closeTx = await program.methods
+      .gameClose()
+      .accounts({
+        game: gamePda,
+        pot: potPda,
+        creator: program.provider.publicKey,
+        metadata: (await getMetadataPda(program.programId))[0],
+        closer: program.provider.publicKey,
+      })
+      .remainingAccounts([...profilePdas, ...game.players].map(pubkey => ({ pubkey, isWritable: true, isSigner: false })))
+      .transaction();
+
+    await program.provider.sendAndConfirm(closeTx);
+
+    const balancesAfter = await Promise.all(game.players.map(p => connection.getBalance(p)));
+    expect(balancesAfter).to.eql(balancesBefore.map(b => b + wager));
+
+    const profilesAfter = await Promise.all(profilePdas.map(p => program.account.playerProfile.fetch(p)));
+    for (const [seat, profile] of profilesAfter.entries()) {
+      expect(profile.ties).to.equal(profilesBefore[seat].ties + 1);
+      expect(profile.wins).to.equal(profilesBefore[seat].wins);
+      expect(profile.losses).to.equal(profilesBefore[seat].losses);
+    }
+
+    expect(await program.account.game.fetchNullable(gamePda)).to.be.null;
+    expect(await program.account.pot.fetchNullable(potPda)).to.be.null;
+  });
+
+  it('join free-to-play game', async () => {
+    const rows = 3;
+    const cols = 3;
+    const connect = 3;
+    const minPlayers = 2;
+    const maxPlayers = 2;
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+
+    const initTx = (rated: boolean) => program.methods
+      .gameInitFree(gameNonce, rows, cols, connect, minPlayers, maxPlayers, rated)
+      .accounts({
+        creator: playerOne.publicKey,
+        game: gamePda,
+        lobby: getLobbyPda(program.programId, 0)[0],
+      })
+      .transaction();
+
+    // nothing is at stake in a free game, so it can't move ratings
+    try {
+      await anchor.web3.sendAndConfirmTransaction(program.provider.connection, await initTx(true), [playerOne], {commitment: 'finalized'});
+      chai.assert(false, "should've failed but didn't ");
+    } catch(err) {
+      expect(err.logs.join()).to.include('FreeGamesCannotBeRated');
+    }
+
+    const txSignature = await anchor.web3.sendAndConfirmTransaction(program.provider.connection, await initTx(false), [playerOne], {commitment: 'finalized'});
+
+    let game = await program.account.game.fetch(gamePda);
+    expect(game.freeToPlay).to.equal(true);
+    expect(game.wager).to.equal(0);
+    expect(game.pot).to.eql(potPda);
+    expect(await provider.connection.getAccountInfo(potPda)).to.be.null;
+
+    const balanceBeforeJoin = await program.provider.connection.getBalance(playerTwo.publicKey);
+    const confirmation = await joinGame(program, playerTwo, {gamePda, potPda});
+    const balanceAfterJoin = await program.provider.connection.getBalance(playerTwo.publicKey);
+
+    game = await program.account.game.fetch(gamePda);
+    expect(game.state).to.eql({ active:{} });
+    expect(game.players).to.have.deep.members([playerOne.publicKey, playerTwo.publicKey]);
+    expect(balanceAfterJoin).to.equal(balanceBeforeJoin - 5000); // only the transaction fee
+    expect(await provider.connection.getAccountInfo(potPda)).to.be.null;
+  });
+
+  it('cancel game', async() => {
+    const rows = 3;
+    const cols = 3;
+    const connect = 3;
+    const minPlayers = 2;
+    const maxPlayers = 2;
+
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+
+    const confirmation = await initGame(program, playerOne, {
+      gameNonce:gameNonce,
+      gamePda: gamePda,
+      potPda: potPda,
+      cols: cols,
+      rows,
+      connect,
+      minPlayers,
+      maxPlayers,
+      wager,
+    });
+  
+    const balanceAfterInit = await program.provider.connection.getBalance(playerOne.publicKey);
+
+    let game = await program.account.game.fetch(gamePda);
+    expect(game.state).to.eql({ waiting:{} });
+
+    const [lobbyPda] = getLobbyPda(program.programId, game.lobbyPage);
+    let lobby = await program.account.lobby.fetch(lobbyPda);
+    expect(lobby.entries.map(e => e.game.toBase58())).to.include(gamePda.toBase58());
+
+    const cancelTx = await program.methods
+      .gameCancel()
+      .accounts({
+        player: playerOne.publicKey,
+        game: gamePda,
+        pot: potPda,
+        lobby: getLobbyPda(program.programId, 0)[0],
+      })
+      .transaction();
+
+    const txSignature = await anchor.web3.sendAndConfirmTransaction(program.provider.connection, cancelTx, [playerOne], {commitment: 'finalized'});
+    const txConfirmation = await program.provider.connection.confirmTransaction(txSignature,'finalized');
+
+    const balanceAfterCancel = await program.provider.connection.getBalance(playerOne.publicKey);
+    expect(balanceAfterCancel).to.be.greaterThan(balanceAfterInit);
+
+    let updatedGame = await program.account.game.fetchNullable(gamePda);
+    expect(updatedGame).to.be.null;
+    let updatedPot = await program.account.pot.fetchNullable(potPda);
+    expect(updatedPot).to.be.null;   
+
+    lobby = await program.account.lobby.fetch(lobbyPda);
+    expect(lobby.entries.map(e => e.game.toBase58())).to.not.include(gamePda.toBase58());
+  });
+
+
+  it('cancel game fail - refund accounts mismatch', async() => {
+    const rows = 3;
+    const cols = 3;
+    const connect = 3;
+    const minPlayers = 2;
+    const maxPlayers = 2;
+
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+
+    const confirmation = await initGame(program, playerOne, {
+      gameNonce:gameNonce,
+      gamePda: gamePda,
+      potPda: potPda,
+      cols: cols,
+      rows,
+      connect,
+      minPlayers,
+      maxPlayers,
+      wager,
+    });
+
+    // nobody but the creator has joined, so there's nobody to refund
+    const cancelTx = await program.methods
+      .gameCancel()
+      .accounts({
+        player: playerOne.publicKey,
+        game: gamePda,
+        pot: potPda,
+        lobby: getLobbyPda(program.programId, 0)[0],
+      })
+      .remainingAccounts([{ pubkey: playerTwo.publicKey, isWritable: true, isSigner: false }])
+      .transaction();
+
+    try {
+      const txSignature = await anchor.web3.sendAndConfirmTransaction(program.provider.connection, cancelTx, [playerOne], {commitment: 'finalized'});
+      chai.assert(false, "should've failed but didn't ");
+    } catch(_err) {
+    }
+
+    let game = await program.account.game.fetch(gamePda);
+    expect(game.state).to.eql({ waiting:{} });
+  });
+
+  it('leave game', async() => {
+    const rows = 3;
+    const cols = 3;
+    const connect = 3;
+    const minPlayers = 2;
+    const maxPlayers = 2;
+
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+
+    const confirmation = await initGame(program, playerOne, {
+      gameNonce:gameNonce,
+      gamePda: gamePda,
+      potPda: potPda,
+      cols: cols,
+      rows,
+      connect,
+      minPlayers,
+      maxPlayers,
+      wager,
+    });
+
+    const leaveTx = (player: Keypair) => program.methods
+      .gameLeave()
+      .accounts({
+        player: player.publicKey,
+        game: gamePda,
+        pot: potPda,
+        lobby: getLobbyPda(program.programId, 0)[0],
+      })
+      .transaction();
+
+    // only the creator is ever seated in a waiting game
+    try {
+      const txSignature = await anchor.web3.sendAndConfirmTransaction(program.provider.connection, await leaveTx(playerTwo), [playerTwo], {commitment: 'finalized'});
+      chai.assert(false, "should've failed but didn't ");
+    } catch(err) {
+      expect(err.logs.join()).to.include('NotAuthorized');
+    }
+
+    const balanceBeforeLeave = await program.provider.connection.getBalance(playerOne.publicKey);
+
+    // the creator leaving cancels the game
+    const txSignature = await anchor.web3.sendAndConfirmTransaction(program.provider.connection, await leaveTx(playerOne), [playerOne], {commitment: 'finalized'});
+
+    const balanceAfterLeave = await program.provider.connection.getBalance(playerOne.publicKey);
+    expect(balanceAfterLeave).to.be.greaterThan(balanceBeforeLeave + wager);
+    expect(await program.account.game.fetchNullable(gamePda)).to.be.null;
+    expect(await program.account.pot.fetchNullable(potPda)).to.be.null;
+  });
+
+  it('expire game fail - not expired', async() => {
+    const rows = 3;
+    const cols = 3;
+    const connect = 3;
+    const minPlayers = 2;
+    const maxPlayers = 2;
+
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+
+    const confirmation = await initGame(program, playerOne, {
+      gameNonce:gameNonce,
+      gamePda: gamePda,
+      potPda: potPda,
+      cols: cols,
+      rows,
+      connect,
+      minPlayers,
+      maxPlayers,
+      wager,
+    });
+
+    const expireTx = await program.methods
+      .gameExpire()
+      .accounts({
+        game: gamePda,
+        pot: potPda,
+        creator: playerOne.publicKey,
+        caller: playerTwo.publicKey,
+        lobby: getLobbyPda(program.programId, 0)[0],
+      })
+      .transaction();
+
+    try {
+      const txSignature = await anchor.web3.sendAndConfirmTransaction(program.provider.connection, expireTx, [playerTwo], {commitment: 'finalized'});
+      chai.assert(false, "should've failed but didn't ");
+    } catch(_err) {
+    }
+
+    let game = await program.account.game.fetch(gamePda);
+    expect(game.state).to.eql({ waiting:{} });
+  });
+
+  it('expire game - refunds the creator, tips the caller and closes', async() => {
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+
+    try {
+      await initGame(program, playerOne, {gameNonce, gamePda, potPda, cols: 3, rows: 3, connect: 3, minPlayers: 2, maxPlayers: 2, wager, lobbyTtlSeconds: 60 * 60 * 24 * 8});
+      chai.assert(false, "should've failed but didn't ");
+    } catch(err) {
+      expect(err.logs.join()).to.include('LobbyTtlOutOfRange');
+    }
+
+    await initGame(program, playerOne, {gameNonce, gamePda, potPda, cols: 3, rows: 3, connect: 3, minPlayers: 2, maxPlayers: 2, wager, lobbyTtlSeconds: 2});
+
+    let game = await program.account.game.fetch(gamePda);
+    expect(game.expiresAt.toNumber()).to.equal(game.initTimestamp.toNumber() + 2);
+
+    //wait for the cluster clock to pass the game's expiry
+    const connection = program.provider.connection;
+    while ((await connection.getBlockTime(await connection.getSlot('finalized'))) <= game.expiresAt.toNumber()) {
+      await new Promise(resolve => setTimeout(resolve, 500));
+    }
+
+    const creatorBalanceBefore = await connection.getBalance(playerOne.publicKey);
+    const callerBalanceBefore = await connection.getBalance(playerTwo.publicKey);
+
+    const expireTx = await program.methods
+      .gameExpire()
+      .accounts({
+        game: gamePda,
+        pot: potPda,
+        creator: playerOne.publicKey,
+        caller: playerTwo.publicKey,
+        lobby: getLobbyPda(program.programId, 0)[0],
+      })
+      .transaction();
+    await anchor.web3.sendAndConfirmTransaction(connection, expireTx, [playerTwo], {commitment: 'finalized'});
+
+    //the caller's tip covers their transaction fee, and the creator gets their wager and the rent back less the tip
+    expect(await connection.getBalance(playerTwo.publicKey)).to.be.greaterThan(callerBalanceBefore);
+    expect(await connection.getBalance(playerOne.publicKey)).to.be.greaterThan(creatorBalanceBefore + wager);
+    expect(await program.account.game.fetchNullable(gamePda)).to.be.null;
+    expect(await program.account.pot.fetchNullable(potPda)).to.be.null;
+
+    const lobby = await program.account.lobby.fetch(getLobbyPda(program.programId, 0)[0]);
+    expect(lobby.entries.map(e => e.game.toBase58())).to.not.include(gamePda.toBase58());
+  });
+
+  it('sponsor pot', async() => {
+    const rows = 3;
+    const cols = 3;
+    const connect = 3;
+    const minPlayers = 2;
+    const maxPlayers = 2;
+    const sponsorship = 250000;
+
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+
+    const confirmation = await initGame(program, playerOne, {
+      gameNonce:gameNonce,
+      gamePda: gamePda,
+      potPda: potPda,
+      cols: cols,
+      rows,
+      connect,
+      minPlayers,
+      maxPlayers,
+      wager,
+    });
+
+    const secondSponsor = anchor.web3.Keypair.generate();
+    await program.provider.sendAndConfirm(new anchor.web3.Transaction().add(
+      anchor.web3.SystemProgram.transfer({ fromPubkey: program.provider.publicKey, toPubkey: secondSponsor.publicKey, lamports: sponsorship * 2 })
+    ));
+
+    const sponsorTx = (sponsor: PublicKey) => program.methods
+      .potSponsor(new anchor.BN(sponsorship))
+      .accounts({
+        game: gamePda,
+        pot: potPda,
+        sponsor,
+      })
+      .transaction();
+
+    // the first sponsor tops up, and a second account adds its own sponsorship
+    const potBalanceBeforeSponsor = await provider.connection.getBalance(potPda);
+    await program.provider.sendAndConfirm(await sponsorTx(program.provider.publicKey));
+    await program.provider.sendAndConfirm(await sponsorTx(program.provider.publicKey));
+    await anchor.web3.sendAndConfirmTransaction(program.provider.connection, await sponsorTx(secondSponsor.publicKey), [secondSponsor], {commitment: 'finalized'});
+
+    const pot = await program.account.pot.fetch(potPda);
+    expect(pot.sponsors.map(s => s.address)).to.eql([program.provider.publicKey, secondSponsor.publicKey]);
+    expect(pot.sponsors.map(s => s.amount.toNumber())).to.eql([sponsorship * 2, sponsorship]);
+    expect(pot.sponsored.toNumber()).to.equal(sponsorship * 3);
+    expect(await provider.connection.getBalance(potPda)).to.equal(potBalanceBeforeSponsor + sponsorship * 3);
+
+    // cancelling returns each sponsorship to its sponsor
+    const sponsorBalancesBeforeCancel = [await provider.connection.getBalance(program.provider.publicKey), await provider.connection.getBalance(secondSponsor.publicKey)];
+    const cancelTx = await program.methods
+      .gameCancel()
+      .accounts({
+        player: playerOne.publicKey,
+        game: gamePda,
+        pot: potPda,
+        lobby: getLobbyPda(program.programId, 0)[0],
+      })
+      .remainingAccounts([
+        { pubkey: program.provider.publicKey, isWritable: true, isSigner: false },
+        { pubkey: secondSponsor.publicKey, isWritable: true, isSigner: false },
+      ])
+      .transaction();
+
+    const txSignature = await anchor.web3.sendAndConfirmTransaction(program.provider.connection, cancelTx, [playerOne], {commitment: 'finalized'});
+
+    expect(await provider.connection.getBalance(program.provider.publicKey)).to.equal(sponsorBalancesBeforeCancel[0] + sponsorship * 2);
+    expect(await provider.connection.getBalance(secondSponsor.publicKey)).to.equal(sponsorBalancesBeforeCancel[1] + sponsorship);
+    expect(await program.account.pot.fetchNullable(potPda)).to.be.null;
+  });
+
+  it('pay the sponsorships to the winner', async() => {
+    const sponsorship = 250000;
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+
+    await initGame(program, playerOne, {gameNonce, gamePda, potPda, cols: 3, rows: 3, connect: 3, minPlayers: 2, maxPlayers: 2, wager});
+
+    // both players sponsor the game on top of their wagers
+    for (const sponsor of [playerOne, playerTwo]) {
+      const sponsorTx = await program.methods
+        .potSponsor(new anchor.BN(sponsorship))
+        .accounts({
+          game: gamePda,
+          pot: potPda,
+          sponsor: sponsor.publicKey,
+        })
+        .transaction();
+      await anchor.web3.sendAndConfirmTransaction(program.provider.connection, sponsorTx, [sponsor], {commitment: 'finalized'});
+    }
+
+    await joinGame(program, playerTwo, {gamePda, potPda});
+
+    const balancesBeforeWin = [await provider.connection.getBalance(playerOne.publicKey), await provider.connection.getBalance(playerTwo.publicKey)];
+    const winner = await playFirstColumnWin(program, [playerOne, playerTwo], gamePda, potPda);
+
+    // the winner made three of the five moves and takes both wagers and both sponsorships
+    const winnerSeat = winner === playerOne ? 0 : 1;
+    expect(await provider.connection.getBalance(winner.publicKey)).to.equal(balancesBeforeWin[winnerSeat] + wager * 2 + sponsorship * 2 - 3 * 5000);
+
+    const pot = await program.account.pot.fetch(potPda);
+    expect(pot.paidOut.toNumber()).to.equal(wager * 2 + sponsorship * 2);
+    expect(pot.refunded.toNumber()).to.equal(0);
+
+    await closeGame(program, winner, { gamePda, potPda, creator: playerOne.publicKey });
+    expect(await program.account.game.fetchNullable(gamePda)).to.be.null;
+  });
+
+  it('win a sponsored game with no wager', async() => {
+    const sponsorship = 250000;
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+
+    await initGame(program, playerOne, {gameNonce, gamePda, potPda, cols: 3, rows: 3, connect: 3, minPlayers: 2, maxPlayers: 2, wager: 0});
+
+    const sponsorTx = await program.methods
+      .potSponsor(new anchor.BN(sponsorship))
+      .accounts({
+        game: gamePda,
+        pot: potPda,
+        sponsor: program.provider.publicKey,
+      })
+      .transaction();
+    await program.provider.sendAndConfirm(sponsorTx);
+
+    await joinGame(program, playerTwo, {gamePda, potPda});
+
+    const balancesBeforeWin = [await provider.connection.getBalance(playerOne.publicKey), await provider.connection.getBalance(playerTwo.publicKey)];
+    const winner = await playFirstColumnWin(program, [playerOne, playerTwo], gamePda, potPda);
+
+    // the winner made three of the five moves, paying a transaction fee for each
+    const winnerSeat = winner === playerOne ? 0 : 1;
+    const winnerBalanceAfterWin = await provider.connection.getBalance(winner.publicKey);
+    expect(winnerBalanceAfterWin).to.equal(balancesBeforeWin[winnerSeat] + sponsorship - 3 * 5000);
+
+    const pot = await program.account.pot.fetch(potPda);
+    expect(pot.paidOut.toNumber()).to.equal(sponsorship);
+
+    await closeGame(program, winner, { gamePda, potPda, creator: playerOne.publicKey });
+    expect(await program.account.game.fetchNullable(gamePda)).to.be.null;
+  });
+
+  it('win a game with a payout table', async() => {
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+
+    await initGame(program, playerOne, {gameNonce, gamePda, potPda, cols: 3, rows: 3, connect: 3, minPlayers: 2, maxPlayers: 2, wager, payoutTable: [70, 30]});
+    let game = await program.account.game.fetch(gamePda);
+    expect(Array.from(game.payoutTable)).to.eql([70, 30]);
+
+    await joinGame(program, playerTwo, {gamePda, potPda});
+
+    game = await program.account.game.fetch(gamePda);
+    const loser = game.currentPlayerIndex == 0 ? playerTwo : playerOne;
+    const loserBalanceBeforeWin = await provider.connection.getBalance(loser.publicKey);
+
+    // the second finishing position isn't the winner, so the loser is passed after the profiles
+    const winner = await playFirstColumnWin(program, [playerOne, playerTwo], gamePda, potPda, [loser.publicKey]);
+    expect(winner).to.not.equal(loser);
+
+    game = await program.account.game.fetch(gamePda);
+    expect(game.placements).to.eql([winner.publicKey, loser.publicKey]);
+
+    // the loser made two of the five moves and takes 30% of the pot, the fee is 0
+    expect(await provider.connection.getBalance(loser.publicKey)).to.equal(loserBalanceBeforeWin + wager * 2 * 30 / 100 - 2 * 5000);
+
+    const pot = await program.account.pot.fetch(potPda);
+    expect(pot.paidOut.toNumber()).to.equal(wager * 2);
+
+    await closeGame(program, winner, { gamePda, potPda, creator: playerOne.publicKey });
+    expect(await program.account.game.fetchNullable(gamePda)).to.be.null;
+  });
+
+  it('horizontal win!', async () => {
+    const rows = 3;
+    const cols = 3;
+    const connect = 3;
+    const minPlayers = 2;
+    const maxPlayers = 2;
+    let moves = 0;
+
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+
+    const initGameConfirmation = await initGame(program, playerOne, {
+      gameNonce,
+      gamePda: gamePda,
+      potPda: potPda,
+      cols: cols,
+      rows,
+      connect,
+      minPlayers,
+      maxPlayers,
+      wager,
+    });
+
+    const joinGameConfirmation = await joinGame(program, playerTwo, { gamePda, potPda});
+    const game = await program.account.game.fetch(gamePda);
+    const players = game.players.map(p=>{ return p.equals(playerOne.publicKey) ? playerOne : playerTwo });
+    let playerIndex = game.currentPlayerIndex;
+
+    await play(program, players[playerIndex],
+        { gamePda: gamePda, potPda: potPda, tile: {row: 0, column: 0}},
+        { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
+          board: [
+            [0,null,null],
+            [null,null,null],
+            [null,null,null]
+          ]
+        }    
+    );
+
+    try {
+      await play(program, 
+        // same player in subsequent turns
+        // change sth about the tx because
+        // duplicate tx that come in too fast
+        // after each other may get dropped
+        players[playerIndex-1],
+        { gamePda: gamePda, potPda: potPda, tile: {row: 1, column: 0}},
+        { moves: moves, playerIndex: playerIndex, state: GameState.active, 
+          board: [
+            [0,null,null],
+            [null,null,null],
+            [null,null,null]
+          ]
+        }    
+      );
+
+      chai.assert(false, "should've failed but didn't ");
+    } catch (_err) {
+    }
+
+    await play(program, players[playerIndex],
+      { gamePda: gamePda, potPda: potPda, tile: {row: 1, column: 0}},
+      { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
+        board: [
+          [0,null,null],
+          [1,null,null],
+          [null,null,null]
+        ]
+      }    
+    );
+
+    await play(program, players[playerIndex],
+      { gamePda: gamePda, potPda: potPda, tile: {row: 0, column: 1}},
+      { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
+        board: [
+          [0,0,null],
+          [1,null,null],
+          [null,null,null]
+        ]
+      }    
+    );
+
+    try {
+      await play(program, players[playerIndex-1],
+        { gamePda: gamePda, potPda: potPda, tile: {row: 5, column: 1}}, // out of bounds row
+        { moves: moves, playerIndex: playerIndex, state: GameState.active, 
+          board: [
+            [0,0,null],
+            [1,null,null],
+            [null,null,null]
+          ]
+        }    
+      );
+
+      chai.assert(false, "should've failed but didn't ");
+    } catch (_err) {
+    }
+
+    await play(program, players[playerIndex],
+      { gamePda: gamePda, potPda: potPda, tile: {row: 1, column: 1}},
+      { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
+        board: [
+          [0,0,null],
+          [1,1,null],
+          [null,null,null]
+        ]
+      }    
+    );
+
+    try {
+      await play(program, players[playerIndex],
+        { gamePda: gamePda, potPda: potPda, tile: {row: 1, column: 1}}, //should cause tile already set error
+        { moves: moves, playerIndex: playerIndex, state: GameState.active, 
+          board: [
+            [0,0,null],
+            [1,1,null],
+            [null,null,null]
+          ]
+        }    
+      );
+
+      chai.assert(false, "should've failed but didn't ");
+    } catch (_err) {
+    }
+
+    await play(program, players[playerIndex],
+      { gamePda: gamePda, potPda: potPda, tile: {row: 0, column: 2}},
+      { moves: ++moves, playerIndex: playerIndex, state: { won: { winner: players[playerIndex].publicKey }, }, 
+        board: [
+          [0,0,0],
+          [1,1,null],
+          [null,null,null]
+        ]
+      }    
+    );
+ 
+
+    try {
+      await play(program, players[playerIndex],
+        { gamePda: gamePda, potPda: potPda, tile: {row: 0, column: 2}}, //should throw an error that the game has already been won
+        { moves: moves, playerIndex: playerIndex, state: { won: { winner: players[playerIndex].publicKey }, }, 
+          board: [
+            [0,0,0],
+            [1,1,null],
+            [null,null,null]
+          ]
+        }    
+      );
+      
+      chai.assert(false, "should've failed but didn't ");
+    } catch (_err) {
+    }
+    
+  });
+
+  it('vertical win!', async () => {
+    const rows = 3;
+    const cols = 3;
+    const connect = 3;
+    const minPlayers = 2;
+    const maxPlayers = 2;
+    let moves = 0;
+
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+
+    const initGameConfirmation = await initGame(program, playerOne, {
+      gameNonce,
+      gamePda: gamePda,
+      potPda: potPda,
+      cols: cols,
+      rows,
+      connect,
+      minPlayers,
+      maxPlayers,
+      wager,
+    });
+
+    const joinGameConfirmation = await joinGame(program, playerTwo, { gamePda, potPda}); 
+    const game = await program.account.game.fetch(gamePda);
+    const players = game.players.map(p=>{ return p.equals(playerOne.publicKey) ? playerOne : playerTwo });
+    let playerIndex = game.currentPlayerIndex;
+
+    await play(program, players[playerIndex],
+        { gamePda: gamePda, potPda: potPda, tile: {row: 0, column: 0}},
+        { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
+          board: [
+            [0,null,null],
+            [null,null,null],
+            [null,null,null]
+          ]
+        }    
+    );    
+    
+    await play(program, players[playerIndex],
+      { gamePda: gamePda, potPda: potPda, tile: {row: 0, column: 1}},
+      { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
+        board: [
+          [0,1,null],
+          [null,null,null],
+          [null,null,null]
+        ]
+      }    
+    );
+
+    await play(program, players[playerIndex],
+      { gamePda: gamePda, potPda: potPda, tile: {row: 1, column: 0}},
+      { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
+        board: [
+          [0,1,null],
+          [0,null,null],
+          [null,null,null]
+        ]
+      }    
+    );
+   
+    await play(program, players[playerIndex],
+      { gamePda: gamePda, potPda: potPda, tile: {row: 1, column: 1}},
+      { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
+        board: [
+          [0,1,null],
+          [0,1,null],
+          [null,null,null]
+        ]
+      }    
+    );
+
+    await play(program, players[playerIndex],
+      { gamePda: gamePda, potPda: potPda, tile: {row: 2, column:0}},
+      { moves: ++moves, playerIndex: playerIndex, state: { won: { winner: players[playerIndex].publicKey }, }, 
+        board: [
+          [0,1,null],
+          [0,1,null],
+          [0,null,null]
+        ]
+      }    
+    );
+ 
+  });
+
+  it('positive slope win!', async () => {
+    const rows = 3;
+    const cols = 3;
+    const connect = 3;
+    const minPlayers = 2;
+    const maxPlayers = 2;
+    let moves = 0;
+
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+
+    const initGameConfirmation = await initGame(program, playerOne, {
+      gameNonce,
+      gamePda: gamePda,
+      potPda: potPda,
+      cols: cols,
+      rows,
+      connect,
+      minPlayers,
+      maxPlayers,
+      wager,
+    });
+
+    const joinGameConfirmation = await joinGame(program, playerTwo, { gamePda, potPda}); 
+    const game = await program.account.game.fetch(gamePda);
+    const players = game.players.map(p=>{ return p.equals(playerOne.publicKey) ? playerOne : playerTwo });
+    let playerIndex = game.currentPlayerIndex;
+
+    await play(program, players[playerIndex],
+        { gamePda: gamePda, potPda: potPda, tile: {row: 2, column: 0}},
+        { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
+          board: [
+            [null,null,null],
+            [null,null,null],
+            [0,null,null]
+          ]
+        }    
+    );    
+    
+    await play(program, players[playerIndex],
+      { gamePda: gamePda, potPda: potPda, tile: {row: 2, column: 1}},
+      { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
+        board: [
+          [null,null,null],
+          [null,null,null],
+          [0,1,null]
+        ]
+      }    
+    );
+
+
+    await play(program, players[playerIndex],
+      { gamePda: gamePda, potPda: potPda, tile: {row: 1, column: 1}},
+      { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
+        board: [
+          [null,null,null],
+          [null,0,null],
+          [0,1,null]
+        ]
+      }    
+    );
+
+    await play(program, players[playerIndex],
+      { gamePda: gamePda, potPda: potPda, tile: {row: 1, column: 0}},
+      { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
+        board: [
+          [null,null,null],
+          [1,0,null],
+          [0,1,null]
+        ]
+      }    
+    ); 
+
+    await play(program, players[playerIndex],
+      { gamePda: gamePda, potPda: potPda, tile: {row: 0, column:2}},
+      { moves: ++moves, playerIndex: playerIndex, state: { won: { winner: players[playerIndex].publicKey }, }, 
+        board: [
+          [null,null,0],
+          [1,0,null],
+          [0,1,null]
+        ]
+      }    
+    );
+
+  });
+
+  it('negative slope win!', async () => {
+    const rows = 3;
+    const cols = 3;
+    const connect = 3;
+    const minPlayers = 2;
+    const maxPlayers = 2;
+    let moves = 0;
+
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+
+    const initGameConfirmation = await initGame(program, playerOne, {
+      gameNonce,
+      gamePda: gamePda,
+      potPda: potPda,
+      cols: cols,
+      rows,
+      connect,
+      minPlayers,
+      maxPlayers,
+      wager,
+    });
+
+    const joinGameConfirmation = await joinGame(program, playerTwo, { gamePda, potPda}); 
+    const game = await program.account.game.fetch(gamePda);
+    const players = game.players.map(p=>{ return p.equals(playerOne.publicKey) ? playerOne : playerTwo });
+    let playerIndex = game.currentPlayerIndex;
+
+    await play(program, players[playerIndex],
+        { gamePda: gamePda, potPda: potPda, tile: {row: 0, column: 0}},
+        { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
+          board: [
+            [0,null,null],
+            [null,null,null],
+            [null,null,null]
+          ]
+        }    
+    );    
+    
+    await play(program, players[playerIndex],
+      { gamePda: gamePda, potPda: potPda, tile: {row: 0, column: 1}},
+      { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
+        board: [
+          [0,1,null],
+          [null,null,null],
+          [null,null,null]
+        ]
+      }    
+    );
+
+ 
+    await play(program, players[playerIndex],
+      { gamePda: gamePda, potPda: potPda, tile: {row: 1, column: 1}},
+      { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
+        board: [
+          [0,1,null],
+          [null,0,null],
+          [null,null,null]
+        ]
+      }    
+    );
+
+    await play(program, players[playerIndex],
+      { gamePda: gamePda, potPda: potPda, tile: {row: 1, column: 0}},
+      { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
+        board: [
+          [0,1,null],
+          [1,0,null],
+          [null,null,null]
+        ]
+      }    
+    ); 
+
+    await play(program, players[playerIndex],
+      { gamePda: gamePda, potPda: potPda, tile: {row: 2, column:2}},
+      { moves: ++moves, playerIndex: playerIndex, state: { won: { winner: players[playerIndex].publicKey }, }, 
+        board: [
+          [0,1,null],
+          [1,0,null],
+          [null,null,0]
+        ]
+      }    
+    );
+  });
+
+  it('close won game', async () => {
+    const rows = 3;
+    const cols = 3;
+    const connect = 3;
+    const minPlayers = 2;
+    const maxPlayers = 2;
+    let moves = 0;
+
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+
+    const initGameConfirmation = await initGame(program, playerOne, {
+      gameNonce,
+      gamePda: gamePda,
+      potPda: potPda,
+      cols: cols,
+      rows,
+      connect,
+      minPlayers,
+      maxPlayers,
+      wager,
+    });
+
+    try {
+      await closeGame(program, playerTwo, { gamePda, potPda, creator: playerOne.publicKey });
+      chai.assert(false, "should've failed but didn't ");
+    } catch (_err) {
+    }
+
+    const joinGameConfirmation = await joinGame(program, playerTwo, { gamePda, potPda}); 
+    const game = await program.account.game.fetch(gamePda);
+    const players = game.players.map(p=>{ return p.equals(playerOne.publicKey) ? playerOne : playerTwo });
+    let playerIndex = game.currentPlayerIndex;
+
+    await play(program, players[playerIndex],
+      { gamePda: gamePda, potPda: potPda, tile: {row: 0, column: 0}},
+      { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
+        board: [
+          [0,null,null],
+          [null,null,null],
+          [null,null,null]
+        ]
+      }    
+    );
+
+    await play(program, players[playerIndex],
+      { gamePda: gamePda, potPda: potPda, tile: {row: 0, column: 1}},
       { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
         board: [
           [0,1,null],
@@ -689,7 +2467,7 @@ describe('connect-squares', () => {
         ]
       }    
     );
-   
+
     await play(program, players[playerIndex],
       { gamePda: gamePda, potPda: potPda, tile: {row: 1, column: 1}},
       { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
@@ -711,19 +2489,251 @@ describe('connect-squares', () => {
         ]
       }    
     );
- 
+
+    const potRent = await provider.connection.getBalance(potPda);
+    const gameRent = await provider.connection.getBalance(gamePda);
+    const creatorBalanceBeforeClose = await provider.connection.getBalance(playerOne.publicKey);
+
+    await closeGame(program, playerTwo, { gamePda, potPda, creator: playerOne.publicKey });
+
+    const creatorBalanceAfterClose = await provider.connection.getBalance(playerOne.publicKey);
+    expect(creatorBalanceAfterClose).to.equal(creatorBalanceBeforeClose + potRent + gameRent);
+    expect(await program.account.game.fetchNullable(gamePda)).to.be.null;
+    expect(await program.account.pot.fetchNullable(potPda)).to.be.null;
+  });
+
+  it('win the jackpot', async () => {
+    const [metadataPda] = await getMetadataPda(program.programId);
+    const [jackpotPda] = await getJackpotPda(program.programId);
+    const setConfig = async (feeBps: number, feeShareBps: number, minCells: number, minWager: number, maxWinMultiple: number) => {
+      const tx = new anchor.web3.Transaction().add(
+        await program.methods
+          .metadataSetFee(feeBps)
+          .accounts({ authority: program.provider.publicKey, metadata: metadataPda })
+          .instruction(),
+        await program.methods
+          .metadataSetJackpot(feeShareBps, minCells, minWager, maxWinMultiple)
+          .accounts({ authority: program.provider.publicKey, metadata: metadataPda })
+          .instruction(),
+      );
+
+      return program.provider.sendAndConfirm(tx);
+    };
+
+    const playJackpotGame = async (gameWager: number) => {
+      const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+      const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+
+      await initGame(program, playerOne, {
+        gameNonce,
+        gamePda: gamePda,
+        potPda: potPda,
+        cols: 3,
+        rows: 3,
+        connect: 3,
+        minPlayers: 2,
+        maxPlayers: 2,
+        wager: gameWager,
+      });
+      await joinGame(program, playerTwo, { gamePda, potPda });
+
+      return playFirstColumnWin(program, [playerOne, playerTwo], gamePda, potPda);
+    };
+
+    await setConfig(1000, 5000, 9, wager, 1);
+
+    //games staking less than the minimum wager feed the jackpot but can't win it
+    const jackpotBefore = await program.account.jackpot.fetch(jackpotPda);
+    await playJackpotGame(wager / 2);
+    let jackpot = await program.account.jackpot.fetch(jackpotPda);
+    expect(jackpot.wins).to.equal(jackpotBefore.wins);
+    expect(jackpot.contributed.toNumber()).to.equal(jackpotBefore.contributed.toNumber() + wager / 2 * 2 / 10 / 2);
+
+    //a win only pays up to what the winning game put in, so the earlier game's share is left for the next winner
+    const jackpotRent = await program.provider.connection.getMinimumBalanceForRentExemption(8 + 53);
+    const gameContribution = wager * 2 / 10 / 2;
+    const jackpotSurplus = (await program.provider.connection.getBalance(jackpotPda)) - jackpotRent + gameContribution;
+    expect(jackpotSurplus).to.be.greaterThan(gameContribution);
+    const winner = await playJackpotGame(wager);
+
+    jackpot = await program.account.jackpot.fetch(jackpotPda);
+    expect(jackpot.wins).to.equal(jackpotBefore.wins + 1);
+    expect(jackpot.lastWinner).to.eql(winner.publicKey);
+    expect(jackpot.paidOut.toNumber()).to.equal(jackpotBefore.paidOut.toNumber() + gameContribution);
+    expect(await program.provider.connection.getBalance(jackpotPda)).to.equal(jackpotRent + jackpotSurplus - gameContribution);
+
+    // later tests expect winners to collect the whole pot
+    await setConfig(0, 0, 0, 0, 0);
+  });
+
+  it('back a player and claim', async () => {
+    const rows = 3;
+    const cols = 3;
+    const connect = 3;
+    const minPlayers = 2;
+    const maxPlayers = 2;
+
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+    const [backingPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        anchor.utils.bytes.utf8.encode("backing"),
+        gamePda.toBuffer(),
+        program.provider.publicKey.toBuffer(),
+      ], program.programId);
+
+    const initGameConfirmation = await initGame(program, playerOne, {
+      gameNonce,
+      gamePda: gamePda,
+      potPda: potPda,
+      cols: cols,
+      rows,
+      connect,
+      minPlayers,
+      maxPlayers,
+      wager,
+    });
+
+    const backTx = await program.methods
+      .gameBack(playerOne.publicKey, new anchor.BN(wager))
+      .accounts({
+        game: gamePda,
+        pot: potPda,
+        backing: backingPda,
+        backer: program.provider.publicKey,
+      })
+      .transaction();
+
+    await program.provider.sendAndConfirm(backTx);
+
+    const joinGameConfirmation = await joinGame(program, playerTwo, { gamePda, potPda});
+
+    // backing stays open for the game's first moves, so the joiner can be backed once they're seated
+    const joinerBacker = anchor.web3.Keypair.generate();
+    await program.provider.sendAndConfirm(new anchor.web3.Transaction().add(
+      anchor.web3.SystemProgram.transfer({ fromPubkey: program.provider.publicKey, toPubkey: joinerBacker.publicKey, lamports: wager * 10 })
+    ));
+    const [joinerBackingPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        anchor.utils.bytes.utf8.encode("backing"),
+        gamePda.toBuffer(),
+        joinerBacker.publicKey.toBuffer(),
+      ], program.programId);
+    const joinerBackTx = await program.methods
+      .gameBack(playerTwo.publicKey, new anchor.BN(wager))
+      .accounts({
+        game: gamePda,
+        pot: potPda,
+        backing: joinerBackingPda,
+        backer: joinerBacker.publicKey,
+      })
+      .transaction();
+    await anchor.web3.sendAndConfirmTransaction(program.provider.connection, joinerBackTx, [joinerBacker]);
+
+    let pot = await program.account.pot.fetch(potPda);
+    expect(pot.backed.toNumber()).to.equal(wager * 2);
+    expect(pot.backerOwed.toNumber()).to.equal(wager * 2);
+    const backing = await program.account.backing.fetch(joinerBackingPda);
+    expect(backing.player).to.eql(playerTwo.publicKey);
+    expect(backing.amount.toNumber()).to.equal(wager);
+
+    // anyone can claim on the backer's behalf
+    const claim = async (backingPda: PublicKey, backer: PublicKey) => {
+      const tx = await program.methods
+        .gameBackClaim()
+        .accounts({
+          game: gamePda,
+          pot: potPda,
+          backing: backingPda,
+          backer,
+        })
+        .transaction();
+
+      return anchor.web3.sendAndConfirmTransaction(program.provider.connection, tx, [playerTwo]);
+    };
+
+    try {
+      await claim(joinerBackingPda, joinerBacker.publicKey);
+      chai.assert(false, "should've failed but didn't ");
+    } catch(err) {
+      expect(err.logs.join()).to.include('GameNotFinished');
+    }
+
+    // the joiner wins whichever seat they were shuffled into
+    const game = await program.account.game.fetch(gamePda);
+    const joinerMovesFirst = game.players[game.currentPlayerIndex].equals(playerTwo.publicKey);
+    const winner = joinerMovesFirst
+      ? await playFirstColumnWin(program, [playerOne, playerTwo], gamePda, potPda)
+      : await playLastColumnWin(program, [playerOne, playerTwo], gamePda, potPda);
+    expect(winner).to.equal(playerTwo);
+
+    // the joiner's seat put in half of its lamports, so its backer shares half of the winnings. the creator's backer loses
+    const backerWinnings = wager * 2;
+    pot = await program.account.pot.fetch(potPda);
+    expect(pot.backerOwed.toNumber()).to.equal(backerWinnings);
+
+    try {
+      await closeGame(program, playerTwo, { gamePda, potPda, creator: playerOne.publicKey });
+      chai.assert(false, "should've failed but didn't ");
+    } catch (err) {
+      expect(err.logs.join()).to.include('BackersUnclaimed');
+    }
+
+    const potBalanceBeforeClaim = await provider.connection.getBalance(potPda);
+    const backerBalanceBeforeClaim = await provider.connection.getBalance(joinerBacker.publicKey);
+    await claim(joinerBackingPda, joinerBacker.publicKey);
+
+    expect(await provider.connection.getBalance(potPda)).to.equal(potBalanceBeforeClaim - backerWinnings);
+    expect(await provider.connection.getBalance(joinerBacker.publicKey)).to.be.greaterThan(backerBalanceBeforeClaim + backerWinnings);
+    expect(await program.account.backing.fetchNullable(joinerBackingPda)).to.be.null;
+
+    await claim(backingPda, program.provider.publicKey);
+    expect(await provider.connection.getBalance(potPda)).to.equal(potBalanceBeforeClaim - backerWinnings);
+    expect(await program.account.backing.fetchNullable(backingPda)).to.be.null;
+
+    // backing closes once the game is over
+    try {
+      const lateBackTx = await program.methods
+        .gameBack(playerTwo.publicKey, new anchor.BN(wager))
+        .accounts({
+          game: gamePda,
+          pot: potPda,
+          backing: joinerBackingPda,
+          backer: joinerBacker.publicKey,
+        })
+        .transaction();
+      await anchor.web3.sendAndConfirmTransaction(program.provider.connection, lateBackTx, [joinerBacker]);
+      chai.assert(false, "should've failed but didn't ");
+    } catch(err) {
+      expect(err.logs.join()).to.include('BackingClosed');
+    }
+
+    await closeGame(program, playerTwo, { gamePda, potPda, creator: playerOne.publicKey });
+    expect(await program.account.game.fetchNullable(gamePda)).to.be.null;
   });
 
-  it('positive slope win!', async () => {
+  it('side bets on a game', async () => {
     const rows = 3;
     const cols = 3;
     const connect = 3;
     const minPlayers = 2;
     const maxPlayers = 2;
-    let moves = 0;
+    const bet = 50000;
 
     const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
     const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+    const [metadataPda] = await getMetadataPda(program.programId);
+    const [betPoolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        anchor.utils.bytes.utf8.encode("bet_pool"),
+        gamePda.toBuffer(),
+      ], program.programId);
+    const getBetPda = (bettor: PublicKey) => anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        anchor.utils.bytes.utf8.encode("bet"),
+        betPoolPda.toBuffer(),
+        bettor.toBuffer(),
+      ], program.programId)[0];
 
     const initGameConfirmation = await initGame(program, playerOne, {
       gameNonce,
@@ -737,76 +2747,123 @@ describe('connect-squares', () => {
       wager,
     });
 
-    const joinGameConfirmation = await joinGame(program, playerTwo, { gamePda, potPda}); 
-    const game = await program.account.game.fetch(gamePda);
-    const players = game.players.map(p=>{ return p.equals(playerOne.publicKey) ? playerOne : playerTwo });
-    let playerIndex = game.currentPlayerIndex;
+    const joinGameConfirmation = await joinGame(program, playerTwo, { gamePda, potPda});
 
-    await play(program, players[playerIndex],
-        { gamePda: gamePda, potPda: potPda, tile: {row: 2, column: 0}},
-        { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
-          board: [
-            [null,null,null],
-            [null,null,null],
-            [0,null,null]
-          ]
-        }    
-    );    
-    
-    await play(program, players[playerIndex],
-      { gamePda: gamePda, potPda: potPda, tile: {row: 2, column: 1}},
-      { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
-        board: [
-          [null,null,null],
-          [null,null,null],
-          [0,1,null]
-        ]
-      }    
-    );
+    const poolTx = await program.methods
+      .betPoolInit(2)
+      .accounts({
+        game: gamePda,
+        betPool: betPoolPda,
+        creator: program.provider.publicKey,
+      })
+      .transaction();
 
+    await program.provider.sendAndConfirm(poolTx);
 
-    await play(program, players[playerIndex],
-      { gamePda: gamePda, potPda: potPda, tile: {row: 1, column: 1}},
-      { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
-        board: [
-          [null,null,null],
-          [null,0,null],
-          [0,1,null]
-        ]
-      }    
-    );
+    const placeBet = async (bettor: Keypair, player: PublicKey) => {
+      const tx = await program.methods
+        .betPlace({ win: { player } }, new anchor.BN(bet))
+        .accounts({
+          game: gamePda,
+          betPool: betPoolPda,
+          bet: getBetPda(bettor.publicKey),
+          bettor: bettor.publicKey,
+        })
+        .transaction();
 
-    await play(program, players[playerIndex],
-      { gamePda: gamePda, potPda: potPda, tile: {row: 1, column: 0}},
-      { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
-        board: [
-          [null,null,null],
-          [1,0,null],
-          [0,1,null]
-        ]
-      }    
-    ); 
+      return anchor.web3.sendAndConfirmTransaction(program.provider.connection, tx, [bettor], {commitment: 'finalized'});
+    };
 
-    await play(program, players[playerIndex],
-      { gamePda: gamePda, potPda: potPda, tile: {row: 0, column:2}},
-      { moves: ++moves, playerIndex: playerIndex, state: { won: { winner: players[playerIndex].publicKey }, }, 
-        board: [
-          [null,null,0],
-          [1,0,null],
-          [0,1,null]
-        ]
-      }    
-    );
+    // players can't bet against themselves
+    try {
+      await placeBet(playerTwo, playerOne.publicKey);
+      chai.assert(false, "should've failed but didn't ");
+    } catch(_err) {
+    }
+
+    // a full board is reset rather than tied, so there's no tie to bet on
+    try {
+      const tieTx = await program.methods
+        .betPlace({ tie: {} }, new anchor.BN(bet))
+        .accounts({
+          game: gamePda,
+          betPool: betPoolPda,
+          bet: getBetPda(program.provider.publicKey),
+          bettor: program.provider.publicKey,
+        })
+        .transaction();
+      await program.provider.sendAndConfirm(tieTx);
+      chai.assert(false, "should've failed but didn't ");
+    } catch(err) {
+      expect(err.logs.join()).to.include('TieBetsNotAvailable');
+    }
+
+    await placeBet(playerTwo, playerTwo.publicKey);
+    await placeBet(playerOne, playerOne.publicKey);
+
+    let betPool = await program.account.betPool.fetch(betPoolPda);
+    expect(betPool.total.toNumber()).to.equal(bet * 2);
+    expect(betPool.openBets).to.equal(2);
+
+    const winner = await playFirstColumnWin(program, [playerOne, playerTwo], gamePda, potPda);
+    const loser = winner.publicKey.equals(playerOne.publicKey) ? playerTwo : playerOne;
+
+    // the pool has to be settled before the game can be closed
+    try {
+      await closeGame(program, playerTwo, { gamePda, potPda, creator: playerOne.publicKey });
+      chai.assert(false, "should've failed but didn't ");
+    } catch (_err) {
+    }
+
+    const settleTx = await program.methods
+      .betPoolSettle()
+      .accounts({
+        game: gamePda,
+        betPool: betPoolPda,
+        metadata: metadataPda,
+        poolCreator: program.provider.publicKey,
+      })
+      .transaction();
+
+    await program.provider.sendAndConfirm(settleTx);
+
+    betPool = await program.account.betPool.fetch(betPoolPda);
+    expect(betPool.settled).to.be.true;
+    expect(betPool.winningOutcome).to.eql({ win: { player: winner.publicKey } });
+
+    const claim = async (bettor: Keypair) => {
+      const tx = await program.methods
+        .betClaim()
+        .accounts({
+          betPool: betPoolPda,
+          bet: getBetPda(bettor.publicKey),
+          poolCreator: program.provider.publicKey,
+          bettor: bettor.publicKey,
+        })
+        .transaction();
 
+      return anchor.web3.sendAndConfirmTransaction(program.provider.connection, tx, [bettor], {commitment: 'finalized'});
+    };
+
+    // the only winning bet takes the whole pool
+    const poolBalanceBeforeClaim = await provider.connection.getBalance(betPoolPda);
+    await claim(winner);
+    expect(await provider.connection.getBalance(betPoolPda)).to.equal(poolBalanceBeforeClaim - bet * 2);
+
+    await claim(loser);
+    expect(await program.account.betPool.fetchNullable(betPoolPda)).to.be.null;
+
+    await closeGame(program, playerTwo, { gamePda, potPda, creator: playerOne.publicKey });
   });
 
-  it('negative slope win!', async () => {
+  it('double accepted then declined', async () => {
     const rows = 3;
     const cols = 3;
     const connect = 3;
     const minPlayers = 2;
     const maxPlayers = 2;
-    let moves = 0;
+    const [metadataPda] = await getMetadataPda(program.programId);
+    const [jackpotPda] = await getJackpotPda(program.programId);
 
     const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
     const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
@@ -824,66 +2881,268 @@ describe('connect-squares', () => {
     });
 
     const joinGameConfirmation = await joinGame(program, playerTwo, { gamePda, potPda}); 
-    const game = await program.account.game.fetch(gamePda);
+    let game = await program.account.game.fetch(gamePda);
     const players = game.players.map(p=>{ return p.equals(playerOne.publicKey) ? playerOne : playerTwo });
-    let playerIndex = game.currentPlayerIndex;
+    const offerer = players[game.currentPlayerIndex];
+    const responder = players[game.currentPlayerIndex ? 0 : 1];
 
-    await play(program, players[playerIndex],
-        { gamePda: gamePda, potPda: potPda, tile: {row: 0, column: 0}},
-        { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
-          board: [
-            [0,null,null],
-            [null,null,null],
-            [null,null,null]
-          ]
-        }    
-    );    
-    
-    await play(program, players[playerIndex],
-      { gamePda: gamePda, potPda: potPda, tile: {row: 0, column: 1}},
-      { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
+    const sendDoubleTx = async (method: 'gameDoubleOffer' | 'gameDoubleAccept', player: Keypair) => {
+      const tx = await program.methods[method]()
+        .accounts({
+          game: gamePda,
+          pot: potPda,
+          player: player.publicKey,
+        })
+        .transaction();
+
+      return anchor.web3.sendAndConfirmTransaction(program.provider.connection, tx, [player], {commitment: 'finalized'});
+    };
+
+    try {
+      await sendDoubleTx('gameDoubleOffer', responder); // not the responder's turn
+      chai.assert(false, "should've failed but didn't ");
+    } catch (_err) {
+    }
+
+    await sendDoubleTx('gameDoubleOffer', offerer);
+    await sendDoubleTx('gameDoubleAccept', responder);
+
+    game = await program.account.game.fetch(gamePda);
+    expect(game.cubeValue).to.equal(2);
+    expect(game.cubeOwner).to.eql(responder.publicKey);
+    expect(game.doubleOfferedBy).to.be.null;
+    expect(game.stakes.map(s => s.toNumber())).to.eql([wager * 2, wager * 2]);
+    expect(game.state).to.eql(GameState.active);
+
+    // the cube now belongs to the responder, so the offerer can't redouble
+    try {
+      await sendDoubleTx('gameDoubleOffer', offerer);
+      chai.assert(false, "should've failed but didn't ");
+    } catch (_err) {
+    }
+
+    // the offerer keeps their turn after the double is accepted
+    await play(program, offerer,
+      { gamePda: gamePda, potPda: potPda, tile: {row: 0, column: 0}},
+      { moves: 1, playerIndex: game.currentPlayerIndex ? 0 : 1, state: GameState.active,
         board: [
-          [0,1,null],
+          [game.currentPlayerIndex,null,null],
           [null,null,null],
           [null,null,null]
         ]
-      }    
+      }
     );
 
- 
-    await play(program, players[playerIndex],
-      { gamePda: gamePda, potPda: potPda, tile: {row: 1, column: 1}},
-      { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
-        board: [
-          [0,1,null],
-          [null,0,null],
-          [null,null,null]
-        ]
-      }    
-    );
+    await sendDoubleTx('gameDoubleOffer', responder);
 
-    await play(program, players[playerIndex],
-      { gamePda: gamePda, potPda: potPda, tile: {row: 1, column: 0}},
-      { moves: ++moves, playerIndex: playerIndex ? --playerIndex : ++playerIndex, state: GameState.active, 
-        board: [
-          [0,1,null],
-          [1,0,null],
-          [null,null,null]
-        ]
-      }    
-    ); 
+    const redoublerBalanceBeforeDecline = await provider.connection.getBalance(responder.publicKey);
+    const declineTx = await program.methods
+      .gameDoubleDecline()
+      .accounts({
+        game: gamePda,
+        pot: potPda,
+        player: offerer.publicKey,
+        winner: responder.publicKey,
+        metadata: metadataPda,
+        jackpot: jackpotPda,
+      })
+      .remainingAccounts(await getProfileAccounts(program, gamePda))
+      .transaction();
 
-    await play(program, players[playerIndex],
-      { gamePda: gamePda, potPda: potPda, tile: {row: 2, column:2}},
-      { moves: ++moves, playerIndex: playerIndex, state: { won: { winner: players[playerIndex].publicKey }, }, 
-        board: [
-          [0,1,null],
-          [1,0,null],
-          [null,null,0]
-        ]
-      }    
-    );
+    await anchor.web3.sendAndConfirmTransaction(program.provider.connection, declineTx, [offerer], {commitment: 'finalized'});
+
+    game = await program.account.game.fetch(gamePda);
+    expect(game.state).to.eql({ won: { winner: responder.publicKey } });
+    expect(game.cubeValue).to.equal(2);
+
+    // the winner collects both doubled stakes plus the redouble they put up
+    const redoublerBalanceAfterDecline = await provider.connection.getBalance(responder.publicKey);
+    expect(redoublerBalanceAfterDecline).to.equal(redoublerBalanceBeforeDecline + wager * 2 * 2 + wager * 2);
+  });
+
+  it('unanswered double is declined by anyone', async () => {
+    const rows = 3;
+    const cols = 3;
+    const connect = 3;
+    const turnSlots = 30;
+    const [metadataPda] = await getMetadataPda(program.programId);
+    const [jackpotPda] = await getJackpotPda(program.programId);
+    const presetSeed = Buffer.concat([Buffer.from([rows, cols, connect, turnSlots]), new anchor.BN(wager).toArrayLike(Buffer, 'be', 4)]);
+    const [queuePda] = anchor.web3.PublicKey.findProgramAddressSync([anchor.utils.bytes.utf8.encode("queue"), presetSeed], program.programId);
+
+    await program.provider.sendAndConfirm(await program.methods
+      .queueInit(rows, cols, connect, wager, turnSlots)
+      .accounts({
+        payer: program.provider.publicKey,
+        queue: queuePda,
+      })
+      .transaction());
+
+    for (const player of [playerOne, playerTwo]) {
+      const tx = await program.methods
+        .queueEnqueue()
+        .accounts({
+          queue: queuePda,
+          profile: getProfilePda(program.programId, player.publicKey)[0],
+          player: player.publicKey,
+        })
+        .transaction();
+
+      await anchor.web3.sendAndConfirmTransaction(program.provider.connection, tx, [player]);
+    }
+
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, program.provider.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+    await program.provider.sendAndConfirm(await program.methods
+      .matchMake(gameNonce, 0, 1)
+      .accounts({
+        queue: queuePda,
+        game: gamePda,
+        pot: potPda,
+        firstProfile: getProfilePda(program.programId, playerOne.publicKey)[0],
+        secondProfile: getProfilePda(program.programId, playerTwo.publicKey)[0],
+        payer: program.provider.publicKey,
+      })
+      .transaction());
+
+    let game = await program.account.game.fetch(gamePda);
+    const players = game.players.map(p=>{ return p.equals(playerOne.publicKey) ? playerOne : playerTwo });
+    const offerer = players[game.currentPlayerIndex];
+    const responder = players[game.currentPlayerIndex ? 0 : 1];
+
+    const offerTx = await program.methods
+      .gameDoubleOffer()
+      .accounts({
+        game: gamePda,
+        pot: potPda,
+        player: offerer.publicKey,
+      })
+      .transaction();
+    await anchor.web3.sendAndConfirmTransaction(program.provider.connection, offerTx, [offerer]);
+
+    const decline = async () => {
+      const tx = await program.methods
+        .gameDoubleDecline()
+        .accounts({
+          game: gamePda,
+          pot: potPda,
+          player: program.provider.publicKey,
+          winner: offerer.publicKey,
+          metadata: metadataPda,
+          jackpot: jackpotPda,
+        })
+        .remainingAccounts(await getProfileAccounts(program, gamePda))
+        .transaction();
+
+      return program.provider.sendAndConfirm(tx);
+    };
+
+    // only the responder can decline until the offer has gone unanswered for a turn
+    try {
+      await decline();
+      chai.assert(false, "should've failed but didn't ");
+    } catch(_err) {
+    }
+
+    game = await program.account.game.fetch(gamePda);
+    while (await program.provider.connection.getSlot() < game.lastMoveSlot.toNumber() + turnSlots) {
+      await new Promise(resolve => setTimeout(resolve, 1000));
+    }
+
+    const offererBalanceBeforeDecline = await provider.connection.getBalance(offerer.publicKey);
+    await decline();
+
+    game = await program.account.game.fetch(gamePda);
+    expect(game.state).to.eql({ won: { winner: offerer.publicKey } });
+    expect(await provider.connection.getBalance(offerer.publicKey)).to.equal(offererBalanceBeforeDecline + wager * 2 + wager);
+
+    await closeGame(program, playerTwo, { gamePda, potPda, creator: program.provider.publicKey });
   });
 
+  it('rematch reuses the game and pot', async () => {
+    const [gamePda, gamePdaBump, gameNonce] = await getGamePda(program, playerOne.publicKey);
+    const [potPda, potPdaBump] = await getPotPda(program.programId, gamePda);
+
+    await initGame(program, playerOne, {
+      gameNonce,
+      gamePda: gamePda,
+      potPda: potPda,
+      cols: 3,
+      rows: 3,
+      connect: 3,
+      minPlayers: 2,
+      maxPlayers: 2,
+      wager,
+    });
+    await joinGame(program, playerTwo, { gamePda, potPda });
+    await playFirstColumnWin(program, [playerOne, playerTwo], gamePda, potPda);
+
+    // bystanders can't close the game while its players may still want a rematch
+    try {
+      const closeTx = await program.methods
+        .gameClose()
+        .accounts({
+          game: gamePda,
+          pot: potPda,
+          creator: playerOne.publicKey,
+          metadata: (await getMetadataPda(program.programId))[0],
+          closer: program.provider.publicKey,
+        })
+        .transaction();
+      await program.provider.sendAndConfirm(closeTx);
+      chai.assert(false, "should've failed but didn't ");
+    } catch(err) {
+      expect(err.logs.join()).to.include('RematchWindowOpen');
+    }
+
+    const [playerOneProfilePda] = getProfilePda(program.programId, playerOne.publicKey);
+    const playerOneGamesPlayed = (await program.account.playerProfile.fetch(playerOneProfilePda)).gamesPlayed;
+
+    const rematch = async (player: Keypair) => {
+      const tx = await program.methods
+        .gameRematch()
+        .accounts({
+          game: gamePda,
+          pot: potPda,
+          player: player.publicKey,
+        })
+        .remainingAccounts(await getProfileAccounts(program, gamePda))
+        .transaction();
+
+      return anchor.web3.sendAndConfirmTransaction(program.provider.connection, tx, [player]);
+    };
+
+    await rematch(playerOne);
+
+    try {
+      await rematch(playerOne);
+      chai.assert(false, "should've failed but didn't ");
+    } catch(err) {
+      expect(err.logs.join()).to.include('AlreadyAgreedToRematch');
+    }
+
+    let game = await program.account.game.fetch(gamePda);
+    expect(game.state).to.have.property('won');
+    expect(game.rematchVotes).to.not.equal(0);
+
+    await rematch(playerTwo);
+
+    game = await program.account.game.fetch(gamePda);
+    expect(game.state).to.eql({ active:{} });
+    expect(game.moves).to.equal(0);
+    expect(game.rematchVotes).to.equal(0);
+    expect(game.board.flat().every(cell => cell === null)).to.be.true;
+    expect((await program.account.playerProfile.fetch(playerOneProfilePda)).gamesPlayed).to.equal(playerOneGamesPlayed + 1);
+
+    let pot = await program.account.pot.fetch(potPda);
+    expect(pot.deposited.toNumber()).to.equal(wager * 2);
+    expect(pot.paidOut.toNumber()).to.equal(0);
+
+    const winner = await playFirstColumnWin(program, [playerOne, playerTwo], gamePda, potPda);
+    game = await program.account.game.fetch(gamePda);
+    expect(game.state).to.eql({ won: { winner: winner.publicKey } });
 
+    await closeGame(program, playerOne, { gamePda, potPda, creator: playerOne.publicKey });
+    expect(await program.account.game.fetchNullable(gamePda)).to.be.null;
+  });
 });